@@ -0,0 +1,40 @@
+use super::MazeGenerator;
+use crate::maze::Maze;
+use rand::prelude::*;
+
+pub struct AldousBroder;
+
+impl MazeGenerator for AldousBroder {
+    fn name(&self) -> &'static str {
+        "aldous-broder"
+    }
+
+    fn description(&self) -> &'static str {
+        "Aldous-Broder: unbiased random walk, uniform spanning tree but slow"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) {
+        aldous_broder(maze, rng);
+    }
+}
+
+pub fn aldous_broder<R: Rng + ?Sized>(maze: &mut Maze, rng: &mut R) {
+    let total = maze.cells.len();
+    if total == 0 {
+        return;
+    }
+
+    let mut current = maze.random_cell(rng);
+    maze.cells[current].visited = true;
+    let mut remaining = total - 1;
+
+    while remaining > 0 {
+        let n_idx = maze.adjacent(current).choose(rng).unwrap();
+        if !maze.cells[n_idx].visited {
+            maze.link(current, n_idx);
+            maze.cells[n_idx].visited = true;
+            remaining -= 1;
+        }
+        current = n_idx;
+    }
+}
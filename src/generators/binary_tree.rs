@@ -0,0 +1,114 @@
+use super::MazeGenerator;
+use crate::maze::{Direction, Maze};
+use rand::prelude::*;
+use std::fmt;
+use std::str::FromStr;
+
+/// The corner that Binary Tree and Sidewinder mazes lean towards.
+///
+/// The two sides that meet in that corner end up as open corridors.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Bias {
+    #[default]
+    NorthEast,
+    NorthWest,
+    SouthEast,
+    SouthWest,
+}
+
+impl Bias {
+    pub const ALL: [Bias; 4] = [
+        Bias::NorthEast,
+        Bias::NorthWest,
+        Bias::SouthEast,
+        Bias::SouthWest,
+    ];
+
+    pub fn vertical(self) -> Direction {
+        match self {
+            Bias::NorthEast | Bias::NorthWest => Direction::North,
+            Bias::SouthEast | Bias::SouthWest => Direction::South,
+        }
+    }
+
+    pub fn horizontal(self) -> Direction {
+        match self {
+            Bias::NorthEast | Bias::SouthEast => Direction::East,
+            Bias::NorthWest | Bias::SouthWest => Direction::West,
+        }
+    }
+}
+
+impl fmt::Display for Bias {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Bias::NorthEast => "north-east",
+            Bias::NorthWest => "north-west",
+            Bias::SouthEast => "south-east",
+            Bias::SouthWest => "south-west",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Bias {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Bias::ALL
+            .into_iter()
+            .find(|bias| bias.to_string() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown bias '{}', expected north-east, north-west, south-east or south-west",
+                    s
+                )
+            })
+    }
+}
+
+#[derive(Default)]
+pub struct BinaryTree {
+    pub bias: Bias,
+}
+
+impl BinaryTree {
+    pub fn new(bias: Bias) -> Self {
+        BinaryTree { bias }
+    }
+}
+
+impl MazeGenerator for BinaryTree {
+    fn name(&self) -> &'static str {
+        "binary-tree"
+    }
+
+    fn description(&self) -> &'static str {
+        "Binary tree: each cell opens towards one of two --bias sides, strong diagonal bias"
+    }
+
+    fn supports(&self, maze: &Maze) -> bool {
+        maze.is_plain_grid()
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) {
+        binary_tree(maze, self.bias, rng);
+    }
+}
+
+pub fn binary_tree<R: Rng + ?Sized>(maze: &mut Maze, bias: Bias, rng: &mut R) {
+    for y in 0..maze.height {
+        for x in 0..maze.width {
+            let vertical = maze.neighbor(x, y, bias.vertical());
+            let horizontal = maze.neighbor(x, y, bias.horizontal());
+
+            let target = match (vertical, horizontal) {
+                (Some(v), Some(h)) => Some(if rng.gen_bool(0.5) { v } else { h }),
+                (v, h) => v.or(h),
+            };
+            if let Some((nx, ny)) = target {
+                maze.remove_wall(x, y, nx, ny);
+            }
+        }
+    }
+}
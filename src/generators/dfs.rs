@@ -0,0 +1,43 @@
+use super::MazeGenerator;
+use crate::maze::Maze;
+use rand::prelude::*;
+
+pub struct Dfs;
+
+impl MazeGenerator for Dfs {
+    fn name(&self) -> &'static str {
+        "dfs"
+    }
+
+    fn description(&self) -> &'static str {
+        "Recursive backtracker: long winding corridors with few branches"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) {
+        dfs(maze, rng);
+    }
+}
+
+pub fn dfs<R: Rng + ?Sized>(maze: &mut Maze, rng: &mut R) {
+    if maze.cells.is_empty() {
+        return;
+    }
+
+    let mut stack = vec![0];
+    maze.cells[0].visited = true;
+
+    while let Some(&idx) = stack.last() {
+        let neighbors: Vec<usize> = maze
+            .adjacent(idx)
+            .filter(|&n_idx| !maze.cells[n_idx].visited)
+            .collect();
+
+        if let Some(&n_idx) = neighbors.choose(rng) {
+            maze.link(idx, n_idx);
+            maze.cells[n_idx].visited = true;
+            stack.push(n_idx);
+        } else {
+            stack.pop();
+        }
+    }
+}
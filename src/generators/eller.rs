@@ -0,0 +1,110 @@
+use super::MazeGenerator;
+use crate::maze::Maze;
+use rand::prelude::*;
+
+pub struct Eller;
+
+impl MazeGenerator for Eller {
+    fn name(&self) -> &'static str {
+        "eller"
+    }
+
+    fn description(&self) -> &'static str {
+        "Eller: builds one row at a time, can stream mazes of any height"
+    }
+
+    fn supports(&self, maze: &Maze) -> bool {
+        maze.is_plain_grid()
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) {
+        eller(maze, rng);
+    }
+}
+
+pub fn eller<R: Rng + ?Sized>(maze: &mut Maze, rng: &mut R) {
+    let width = maze.width;
+    for (y, row) in EllerRows::new(maze.width, maze.height, rng).enumerate() {
+        for (x, walls) in row.into_iter().enumerate() {
+            maze.cells[y * width + x].walls[..4].copy_from_slice(&walls);
+        }
+    }
+}
+
+/// Generates a maze row by row with Eller's algorithm.
+///
+/// Each item holds the walls of one row, indexed like [`Cell::walls`](crate::Cell::walls).
+/// Only the current row is kept in memory, so the height is unbounded.
+pub struct EllerRows<'a, R: Rng + ?Sized> {
+    width: usize,
+    height: usize,
+    row: usize,
+    sets: Vec<usize>,
+    north: Vec<bool>,
+    next_set: usize,
+    rng: &'a mut R,
+}
+
+impl<'a, R: Rng + ?Sized> EllerRows<'a, R> {
+    pub fn new(width: usize, height: usize, rng: &'a mut R) -> Self {
+        EllerRows {
+            width,
+            height,
+            row: 0,
+            sets: vec![0; width],
+            north: vec![true; width],
+            next_set: 0,
+            rng,
+        }
+    }
+}
+
+impl<R: Rng + ?Sized> Iterator for EllerRows<'_, R> {
+    type Item = Vec<[bool; 4]>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row >= self.height {
+            return None;
+        }
+        let last = self.row + 1 == self.height;
+
+        for x in 0..self.width {
+            if self.north[x] {
+                self.sets[x] = self.next_set;
+                self.next_set += 1;
+            }
+        }
+
+        let mut east = vec![true; self.width];
+        for x in 1..self.width {
+            if self.sets[x - 1] != self.sets[x] && (last || self.rng.gen_bool(0.5)) {
+                east[x - 1] = false;
+                let (from, to) = (self.sets[x], self.sets[x - 1]);
+                for set in self.sets.iter_mut().filter(|set| **set == from) {
+                    *set = to;
+                }
+            }
+        }
+
+        let mut south = vec![true; self.width];
+        if !last {
+            let mut columns: Vec<usize> = (0..self.width).collect();
+            columns.sort_by_key(|&x| self.sets[x]);
+            for group in columns.chunk_by(|&a, &b| self.sets[a] == self.sets[b]) {
+                for &x in group {
+                    south[x] = !self.rng.gen_bool(0.5);
+                }
+                if group.iter().all(|&x| south[x]) {
+                    south[*group.choose(self.rng).unwrap()] = false;
+                }
+            }
+        }
+
+        let walls = (0..self.width)
+            .map(|x| [self.north[x], east[x], south[x], x == 0 || east[x - 1]])
+            .collect();
+        self.north = south;
+        self.row += 1;
+        Some(walls)
+    }
+}
@@ -0,0 +1,165 @@
+use super::MazeGenerator;
+use crate::maze::Maze;
+use rand::prelude::*;
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+/// How the Growing Tree algorithm picks the next active cell to grow from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Selection {
+    /// The most recently added cell, which behaves like [`dfs`](super::dfs).
+    #[default]
+    Newest,
+    /// The cell that has been active the longest, giving long straight runs.
+    Oldest,
+    /// Any active cell, which behaves like [`prim`](super::prim).
+    Random,
+    /// The cell halfway through the active list.
+    Middle,
+    /// A weighted random choice between the listed strategies on every step.
+    /// The weights must not all be zero and must add up to at most
+    /// `u32::MAX`.
+    Mix(Vec<(Selection, u32)>),
+}
+
+impl Selection {
+    fn pick<R: Rng + ?Sized>(&self, len: usize, rng: &mut R) -> usize {
+        match self {
+            Selection::Newest => len - 1,
+            Selection::Oldest => 0,
+            Selection::Random => rng.gen_range(0..len),
+            Selection::Middle => len / 2,
+            Selection::Mix(choices) => {
+                let (selection, _) = choices.choose_weighted(rng, |&(_, weight)| weight).unwrap();
+                selection.pick(len, rng)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Selection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Selection::Newest => write!(f, "newest"),
+            Selection::Oldest => write!(f, "oldest"),
+            Selection::Random => write!(f, "random"),
+            Selection::Middle => write!(f, "middle"),
+            Selection::Mix(choices) => {
+                write!(f, "mix:")?;
+                for (i, (selection, weight)) in choices.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}={}", selection, weight)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl FromStr for Selection {
+    type Err = String;
+
+    /// Parses `newest`, `oldest`, `random`, `middle` or a weighted mix such as
+    /// `mix:newest=75,random=25`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "newest" => Ok(Selection::Newest),
+            "oldest" => Ok(Selection::Oldest),
+            "random" => Ok(Selection::Random),
+            "middle" => Ok(Selection::Middle),
+            _ => {
+                let Some(list) = s.strip_prefix("mix:") else {
+                    return Err(format!(
+                        "unknown selection '{}', expected newest, oldest, random, middle or mix:<name>=<weight>,...",
+                        s
+                    ));
+                };
+
+                let mut choices = Vec::new();
+                for entry in list.split(',') {
+                    let (name, weight) = entry.split_once('=').ok_or_else(|| {
+                        format!("missing weight in '{}', expected <name>=<weight>", entry)
+                    })?;
+                    let selection: Selection = name.parse()?;
+                    if let Selection::Mix(_) = selection {
+                        return Err("a mix cannot contain another mix".to_string());
+                    }
+                    let weight = weight
+                        .parse()
+                        .map_err(|_| format!("invalid weight '{}' for {}", weight, name))?;
+                    choices.push((selection, weight));
+                }
+
+                let total = choices
+                    .iter()
+                    .try_fold(0u32, |total, &(_, weight)| total.checked_add(weight))
+                    .ok_or_else(|| {
+                        format!("the weights of a mix add up to more than {}", u32::MAX)
+                    })?;
+                if total == 0 {
+                    return Err("a mix needs at least one non-zero weight".to_string());
+                }
+                Ok(Selection::Mix(choices))
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct GrowingTree {
+    pub selection: Selection,
+}
+
+impl GrowingTree {
+    pub fn new(selection: Selection) -> Self {
+        GrowingTree { selection }
+    }
+}
+
+impl MazeGenerator for GrowingTree {
+    fn name(&self) -> &'static str {
+        "growing-tree"
+    }
+
+    fn description(&self) -> &'static str {
+        "Growing tree: grows from active cells picked by --selection, between dfs and prim"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) {
+        growing_tree(maze, &self.selection, rng);
+    }
+}
+
+pub fn growing_tree<R: Rng + ?Sized>(maze: &mut Maze, selection: &Selection, rng: &mut R) {
+    if maze.cells.is_empty() {
+        return;
+    }
+
+    let start = maze.random_cell(rng);
+    maze.cells[start].visited = true;
+    let mut active = VecDeque::from([start]);
+
+    while !active.is_empty() {
+        let pick = selection.pick(active.len(), rng);
+        let idx = active[pick];
+
+        let unvisited = maze
+            .adjacent(idx)
+            .filter(|&n_idx| !maze.cells[n_idx].visited)
+            .choose(rng);
+
+        match unvisited {
+            Some(n_idx) => {
+                maze.link(idx, n_idx);
+                maze.cells[n_idx].visited = true;
+                active.push_back(n_idx);
+            }
+            None => {
+                active.remove(pick);
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use super::MazeGenerator;
+use crate::maze::Maze;
+use rand::prelude::*;
+
+pub struct HuntAndKill;
+
+impl MazeGenerator for HuntAndKill {
+    fn name(&self) -> &'static str {
+        "hunt-and-kill"
+    }
+
+    fn description(&self) -> &'static str {
+        "Hunt-and-kill: long corridors like dfs without keeping a stack"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) {
+        hunt_and_kill(maze, rng);
+    }
+}
+
+pub fn hunt_and_kill<R: Rng + ?Sized>(maze: &mut Maze, rng: &mut R) {
+    if maze.cells.is_empty() {
+        return;
+    }
+
+    let start = maze.random_cell(rng);
+    maze.cells[start].visited = true;
+    let mut current = Some(start);
+    // Cells before this one are all visited, so hunting can skip them.
+    let mut hunt_from = 0;
+
+    while let Some(idx) = current {
+        let unvisited = maze
+            .adjacent(idx)
+            .filter(|&n_idx| !maze.cells[n_idx].visited)
+            .choose(rng);
+
+        current = match unvisited {
+            Some(n_idx) => {
+                maze.link(idx, n_idx);
+                maze.cells[n_idx].visited = true;
+                Some(n_idx)
+            }
+            None => hunt(maze, &mut hunt_from, rng),
+        };
+    }
+}
+
+fn hunt<R: Rng + ?Sized>(maze: &mut Maze, hunt_from: &mut usize, rng: &mut R) -> Option<usize> {
+    let mut first_unvisited = None;
+
+    for idx in *hunt_from..maze.cells.len() {
+        if maze.cells[idx].visited {
+            continue;
+        }
+        let first = *first_unvisited.get_or_insert(idx);
+
+        let visited = maze
+            .adjacent(idx)
+            .filter(|&n_idx| maze.cells[n_idx].visited)
+            .choose(rng);
+        if let Some(n_idx) = visited {
+            maze.link(idx, n_idx);
+            maze.cells[idx].visited = true;
+            *hunt_from = first;
+            return Some(idx);
+        }
+    }
+    None
+}
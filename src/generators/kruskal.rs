@@ -0,0 +1,60 @@
+use super::MazeGenerator;
+use crate::maze::Maze;
+use rand::prelude::*;
+
+pub struct Kruskal;
+
+impl MazeGenerator for Kruskal {
+    fn name(&self) -> &'static str {
+        "kruskal"
+    }
+
+    fn description(&self) -> &'static str {
+        "Randomized Kruskal: joins random cells until one tree spans the grid"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) {
+        kruskal(maze, rng);
+    }
+}
+
+pub fn kruskal<R: Rng + ?Sized>(maze: &mut Maze, rng: &mut R) {
+    let mut sets: Vec<usize> = (0..maze.cells.len()).collect();
+    // Every inner wall once, as a cell and the slot of the wall, listed from
+    // the cell with the lower index.
+    let mut walls: Vec<(usize, usize)> = Vec::new();
+
+    for idx in 0..maze.cells.len() {
+        for slot in maze.cells[idx].slots() {
+            if maze.neighbor_at(idx, slot).is_some_and(|n_idx| n_idx > idx) {
+                walls.push((idx, slot));
+            }
+        }
+    }
+
+    walls.shuffle(rng);
+
+    for (idx, slot) in walls {
+        let n_idx = maze.neighbor_at(idx, slot).unwrap();
+        let set1 = find(&mut sets, idx);
+        let set2 = find(&mut sets, n_idx);
+
+        if set1 != set2 {
+            maze.set_slot(idx, slot, false);
+            union(&mut sets, set1, set2);
+        }
+    }
+}
+
+fn find(sets: &mut Vec<usize>, x: usize) -> usize {
+    if sets[x] != x {
+        sets[x] = find(sets, sets[x]);
+    }
+    sets[x]
+}
+
+fn union(sets: &mut Vec<usize>, x: usize, y: usize) {
+    let root_x = find(sets, x);
+    let root_y = find(sets, y);
+    sets[root_x] = root_y;
+}
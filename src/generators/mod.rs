@@ -0,0 +1,102 @@
+use crate::maze::Maze;
+use rand::RngCore;
+
+mod aldous_broder;
+mod binary_tree;
+mod dfs;
+mod eller;
+mod growing_tree;
+mod hunt_and_kill;
+mod kruskal;
+mod prim;
+mod recursive_division;
+mod sidewinder;
+mod wilson;
+
+pub use aldous_broder::{aldous_broder, AldousBroder};
+pub use binary_tree::{binary_tree, Bias, BinaryTree};
+pub use dfs::{dfs, Dfs};
+pub use eller::{eller, Eller, EllerRows};
+pub use growing_tree::{growing_tree, GrowingTree, Selection};
+pub use hunt_and_kill::{hunt_and_kill, HuntAndKill};
+pub use kruskal::{kruskal, Kruskal};
+pub use prim::{prim, Prim};
+pub use recursive_division::{recursive_division, RecursiveDivision};
+pub use sidewinder::{sidewinder, Sidewinder};
+pub use wilson::{wilson, Wilson};
+
+/// A maze generation algorithm that can be selected by name.
+pub trait MazeGenerator {
+    /// Identifier used on the command line, e.g. `"kruskal"`.
+    fn name(&self) -> &'static str;
+
+    /// One-line summary shown in `--help`.
+    fn description(&self) -> &'static str;
+
+    /// Whether the algorithm can build `maze`. Algorithms that work on rows
+    /// and columns of square cells only support
+    /// [plain grids](Maze::is_plain_grid) without wrapped edges.
+    fn supports(&self, _maze: &Maze) -> bool {
+        true
+    }
+
+    /// Builds the maze in `maze`, which starts with every wall in place.
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore);
+}
+
+/// The set of generators known to the command line and the benchmark.
+pub struct Registry {
+    generators: Vec<Box<dyn MazeGenerator>>,
+}
+
+impl Registry {
+    /// A registry holding every built-in generator.
+    pub fn new() -> Self {
+        let mut registry = Registry::empty();
+        registry.register(Kruskal);
+        registry.register(Prim);
+        registry.register(Dfs);
+        registry.register(Wilson);
+        registry.register(AldousBroder);
+        registry.register(Eller);
+        registry.register(RecursiveDivision);
+        registry.register(GrowingTree::default());
+        registry.register(HuntAndKill);
+        registry.register(BinaryTree::default());
+        registry.register(Sidewinder::default());
+        registry
+    }
+
+    pub fn empty() -> Self {
+        Registry {
+            generators: Vec::new(),
+        }
+    }
+
+    /// Adds `generator`, replacing any generator previously registered under the same name.
+    pub fn register<G: MazeGenerator + 'static>(&mut self, generator: G) {
+        let generator: Box<dyn MazeGenerator> = Box::new(generator);
+        match self
+            .generators
+            .iter()
+            .position(|existing| existing.name() == generator.name())
+        {
+            Some(pos) => self.generators[pos] = generator,
+            None => self.generators.push(generator),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn MazeGenerator> {
+        self.iter().find(|generator| generator.name() == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn MazeGenerator> {
+        self.generators.iter().map(|generator| generator.as_ref())
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Registry::new()
+    }
+}
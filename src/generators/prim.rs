@@ -0,0 +1,42 @@
+use super::MazeGenerator;
+use crate::maze::Maze;
+use rand::prelude::*;
+
+pub struct Prim;
+
+impl MazeGenerator for Prim {
+    fn name(&self) -> &'static str {
+        "prim"
+    }
+
+    fn description(&self) -> &'static str {
+        "Randomized Prim: grows the maze from a random frontier, many short dead ends"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) {
+        prim(maze, rng);
+    }
+}
+
+pub fn prim<R: Rng + ?Sized>(maze: &mut Maze, rng: &mut R) {
+    if maze.cells.is_empty() {
+        return;
+    }
+
+    let start = maze.random_cell(rng);
+    let mut frontier = vec![start];
+    maze.cells[start].visited = true;
+
+    while !frontier.is_empty() {
+        let idx = frontier.swap_remove(rng.gen_range(0..frontier.len()));
+
+        let neighbors: Vec<usize> = maze.adjacent(idx).collect();
+        for n_idx in neighbors {
+            if !maze.cells[n_idx].visited {
+                maze.link(idx, n_idx);
+                maze.cells[n_idx].visited = true;
+                frontier.push(n_idx);
+            }
+        }
+    }
+}
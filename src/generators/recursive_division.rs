@@ -0,0 +1,70 @@
+use super::MazeGenerator;
+use crate::maze::Maze;
+use rand::prelude::*;
+
+pub struct RecursiveDivision;
+
+impl MazeGenerator for RecursiveDivision {
+    fn name(&self) -> &'static str {
+        "recursive-division"
+    }
+
+    fn description(&self) -> &'static str {
+        "Recursive division: splits an open grid with walls, long straight corridors"
+    }
+
+    fn supports(&self, maze: &Maze) -> bool {
+        maze.is_plain_grid()
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) {
+        recursive_division(maze, rng);
+    }
+}
+
+pub fn recursive_division<R: Rng + ?Sized>(maze: &mut Maze, rng: &mut R) {
+    for y in 0..maze.height {
+        for x in 0..maze.width {
+            if x + 1 < maze.width {
+                maze.remove_wall(x, y, x + 1, y);
+            }
+            if y + 1 < maze.height {
+                maze.remove_wall(x, y, x, y + 1);
+            }
+        }
+    }
+
+    // Regions still to be divided as (x, y, width, height). An explicit stack
+    // keeps very elongated mazes from overflowing the call stack.
+    let mut regions = vec![(0, 0, maze.width, maze.height)];
+
+    while let Some((x, y, width, height)) = regions.pop() {
+        if width < 2 || height < 2 {
+            continue;
+        }
+
+        let horizontal = if width == height {
+            rng.gen_bool(0.5)
+        } else {
+            height > width
+        };
+
+        if horizontal {
+            let wall_y = y + rng.gen_range(0..height - 1);
+            let passage_x = x + rng.gen_range(0..width);
+            for cx in (x..x + width).filter(|&cx| cx != passage_x) {
+                maze.add_wall(cx, wall_y, cx, wall_y + 1);
+            }
+            regions.push((x, y, width, wall_y - y + 1));
+            regions.push((x, wall_y + 1, width, y + height - wall_y - 1));
+        } else {
+            let wall_x = x + rng.gen_range(0..width - 1);
+            let passage_y = y + rng.gen_range(0..height);
+            for cy in (y..y + height).filter(|&cy| cy != passage_y) {
+                maze.add_wall(wall_x, cy, wall_x + 1, cy);
+            }
+            regions.push((x, y, wall_x - x + 1, height));
+            regions.push((wall_x + 1, y, x + width - wall_x - 1, height));
+        }
+    }
+}
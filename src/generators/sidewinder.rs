@@ -0,0 +1,67 @@
+use super::{Bias, MazeGenerator};
+use crate::maze::{Direction, Maze};
+use rand::prelude::*;
+
+#[derive(Default)]
+pub struct Sidewinder {
+    pub bias: Bias,
+}
+
+impl Sidewinder {
+    pub fn new(bias: Bias) -> Self {
+        Sidewinder { bias }
+    }
+}
+
+impl MazeGenerator for Sidewinder {
+    fn name(&self) -> &'static str {
+        "sidewinder"
+    }
+
+    fn description(&self) -> &'static str {
+        "Sidewinder: horizontal runs joined towards the --bias side, one straight corridor"
+    }
+
+    fn supports(&self, maze: &Maze) -> bool {
+        maze.is_plain_grid()
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) {
+        sidewinder(maze, self.bias, rng);
+    }
+}
+
+pub fn sidewinder<R: Rng + ?Sized>(maze: &mut Maze, bias: Bias, rng: &mut R) {
+    let mut run = Vec::new();
+
+    for y in 0..maze.height {
+        let columns: Vec<usize> = if bias.horizontal() == Direction::East {
+            (0..maze.width).collect()
+        } else {
+            (0..maze.width).rev().collect()
+        };
+
+        for x in columns {
+            let vertical = maze.neighbor(x, y, bias.vertical());
+            let horizontal = maze.neighbor(x, y, bias.horizontal());
+
+            if vertical.is_none() {
+                if let Some((nx, ny)) = horizontal {
+                    maze.remove_wall(x, y, nx, ny);
+                }
+                continue;
+            }
+
+            run.push(x);
+            match horizontal {
+                Some((nx, ny)) if rng.gen_bool(0.5) => maze.remove_wall(x, y, nx, ny),
+                _ => {
+                    let &member = run.choose(rng).unwrap();
+                    let (nx, ny) = maze.neighbor(member, y, bias.vertical()).unwrap();
+                    maze.remove_wall(member, y, nx, ny);
+                    run.clear();
+                }
+            }
+        }
+    }
+}
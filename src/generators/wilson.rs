@@ -0,0 +1,48 @@
+use super::MazeGenerator;
+use crate::maze::Maze;
+use rand::prelude::*;
+
+pub struct Wilson;
+
+impl MazeGenerator for Wilson {
+    fn name(&self) -> &'static str {
+        "wilson"
+    }
+
+    fn description(&self) -> &'static str {
+        "Wilson: loop-erased random walks, uniform spanning tree"
+    }
+
+    fn generate(&self, maze: &mut Maze, rng: &mut dyn RngCore) {
+        wilson(maze, rng);
+    }
+}
+
+pub fn wilson<R: Rng + ?Sized>(maze: &mut Maze, rng: &mut R) {
+    let total = maze.cells.len();
+    if total == 0 {
+        return;
+    }
+
+    // The last exit taken from every cell of the current walk. Overwriting it
+    // when the walk revisits a cell is what erases the loop.
+    let mut next = vec![0; total];
+    let root = rng.gen_range(0..total);
+    maze.cells[root].visited = true;
+
+    for start in 0..total {
+        let mut current = start;
+        while !maze.cells[current].visited {
+            next[current] = maze.adjacent(current).choose(rng).unwrap();
+            current = next[current];
+        }
+
+        let mut current = start;
+        while !maze.cells[current].visited {
+            let following = next[current];
+            maze.link(current, following);
+            maze.cells[current].visited = true;
+            current = following;
+        }
+    }
+}
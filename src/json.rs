@@ -0,0 +1,286 @@
+//! A JSON representation of a [`Maze`] for tools written in other languages.
+//!
+//! ```json
+//! {
+//!   "topology": "square",
+//!   "width": 3,
+//!   "height": 2,
+//!   "seed": 42,
+//!   "algorithm": "kruskal",
+//!   "entrance": [0, 0],
+//!   "exit": [2, 1],
+//!   "walls": [[12, 1, 7], [13, 4, 3]],
+//!   "metrics": {
+//!     "dead_ends": 2,
+//!     "longest_path": 3,
+//!     "avg_path_length": 0.8,
+//!     "branching_factor": 2.0,
+//!     "quality_index": 0.75
+//!   }
+//! }
+//! ```
+//!
+//! `walls` holds one array per row, top to bottom, with one number per cell,
+//! left to right. Bit 0 of the number is set when the cell has a wall on its
+//! north side, bit 1 east, bit 2 south and bit 3 west, so `15` is a closed
+//! cell and `0` a cell open on every side. On a `"hex"` maze the six bits
+//! from 0 to 5 stand for the sides north, north-east, south-east, south,
+//! south-west and north-west. The wall between two neighboring cells appears
+//! in both of them and must agree; missing outer walls are the entrance and
+//! exit openings.
+//!
+//! On a `"delta"` maze bits 0 to 2 stand for the east, south and west sides
+//! of a triangle pointing up, where `x + y` is even, and for the north, east
+//! and west sides of one pointing down.
+//!
+//! On a `"polar"` maze every row is a ring, from the center cell outwards,
+//! and `width` is the number of cells of the outer ring. Bits 0 to 2 of a
+//! ring cell stand for its clockwise, inward and counterclockwise walls and
+//! the following one or two bits for its outward walls, clockwise; the bits
+//! of the center cell stand for the walls to the cells of the first ring.
+//!
+//! A square maze whose edges wrap around has a `wrap` of `"horizontal"`,
+//! `"vertical"`, `"both"` or `"mobius"`, see [`Wrap`]; the walls
+//! on joined edges are shared with the cells on the opposite edge like any
+//! other wall between neighbors. Plain mazes leave `wrap` out.
+//!
+//! A square maze with several levels has a `depth`, the number of levels,
+//! and `height` rows of `walls` per level, the levels one after the other.
+//! Bits 4 and 5 of its cells stand for the ceiling and the floor, clear
+//! where stairs lead up to the previous level or down to the next one.
+//! `depth` defaults to 1 and is left out for mazes of a single level.
+//!
+//! `topology` defaults to `"square"`. `seed`, `algorithm`, `entrance`, `exit`
+//! and `metrics` may be missing or `null`. `metrics` is written for
+//! convenience only and is recomputed from the walls when a maze is loaded.
+//! Seeds are unsigned 64-bit integers, which some JSON parsers can only read
+//! exactly as 64-bit or arbitrary precision integers.
+
+use crate::maze::Maze;
+use crate::quality::calculate_quality_index;
+use crate::topology::{polar_rings, Topology, Wrap};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+
+/// The top-level JSON object.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MazeDocument {
+    #[serde(default)]
+    pub topology: Topology,
+    #[serde(default, skip_serializing_if = "Wrap::is_none")]
+    pub wrap: Wrap,
+    pub width: usize,
+    pub height: usize,
+    #[serde(default = "one", skip_serializing_if = "is_one")]
+    pub depth: usize,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub algorithm: Option<String>,
+    #[serde(default)]
+    pub entrance: Option<(usize, usize)>,
+    #[serde(default)]
+    pub exit: Option<(usize, usize)>,
+    pub walls: Vec<Vec<u8>>,
+    #[serde(default)]
+    pub metrics: Option<Metrics>,
+}
+
+/// [`MazeQuality`](crate::MazeQuality) and the quality index, as stored in a [`MazeDocument`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Metrics {
+    pub dead_ends: usize,
+    pub longest_path: usize,
+    pub avg_path_length: f64,
+    pub branching_factor: f64,
+    pub quality_index: f64,
+}
+
+impl MazeDocument {
+    /// Describes `maze`, measuring its metrics. `seed` and `algorithm` are
+    /// left empty for the caller to fill in.
+    pub fn new(maze: &Maze) -> Self {
+        let quality = maze.measure_quality();
+        let walls = (0..maze.rows())
+            .map(|y| {
+                (0..maze.row_len(y))
+                    .map(|x| wall_bits(maze.cell(x, y).walls()))
+                    .collect()
+            })
+            .collect();
+
+        MazeDocument {
+            topology: maze.topology(),
+            wrap: maze.wrap(),
+            // A maze without rows has no cells to be wide, whatever it was
+            // created with.
+            width: if maze.rows() == 0 { 0 } else { maze.width },
+            height: maze.height,
+            depth: maze.depth,
+            seed: None,
+            algorithm: None,
+            entrance: maze.entrance,
+            exit: maze.exit,
+            walls,
+            metrics: Some(Metrics {
+                dead_ends: quality.dead_ends,
+                longest_path: quality.longest_path,
+                avg_path_length: quality.avg_path_length,
+                branching_factor: quality.branching_factor,
+                quality_index: calculate_quality_index(&quality, maze.cells.len()),
+            }),
+        }
+    }
+
+    /// Rebuilds the maze, checking that the walls fit the dimensions and that
+    /// neighboring cells agree on the walls between them.
+    pub fn to_maze(&self) -> Result<Maze, String> {
+        if self.depth == 0 {
+            return Err("a maze needs at least one level".to_string());
+        }
+        let rows = self
+            .height
+            .checked_mul(self.depth)
+            .filter(|&rows| rows == self.walls.len())
+            .ok_or_else(|| match self.depth {
+                1 => format!(
+                    "expected {} rows of walls, found {}",
+                    self.height,
+                    self.walls.len()
+                ),
+                _ => format!(
+                    "expected {} levels of {} rows of walls, found {} rows",
+                    self.depth,
+                    self.height,
+                    self.walls.len()
+                ),
+            })?;
+        if !self.wrap.is_none() && self.topology != Topology::Square {
+            return Err(format!("a {} maze cannot wrap", self.topology));
+        }
+        self.topology.check_size(self.width, self.height)?;
+        // Check the rows against the dimensions before allocating any cells,
+        // so a document cannot claim a far larger maze than it describes.
+        // Without rows there are no cells to check the width against.
+        if rows == 0 && self.width != 0 {
+            return Err(format!(
+                "a maze without rows of walls must be 0 cells wide, not {}",
+                self.width
+            ));
+        }
+        let lens = match self.topology {
+            Topology::Polar => polar_rings(rows),
+            _ => vec![self.width; rows],
+        };
+        if lens.last().is_some_and(|&len| len != self.width) {
+            return Err(format!(
+                "a {} maze with {} rings is {} cells wide, not {}",
+                self.topology,
+                self.height,
+                lens[rows - 1],
+                self.width
+            ));
+        }
+        for (y, (row, &len)) in self.walls.iter().zip(&lens).enumerate() {
+            if row.len() != len {
+                return Err(format!(
+                    "expected {} cells in row {}, found {}",
+                    len,
+                    y,
+                    row.len()
+                ));
+            }
+        }
+        let mut maze = match self.depth {
+            1 => Maze::with_topology(self.topology, self.width, self.height),
+            _ if self.topology != Topology::Square => {
+                return Err(format!("a {} maze cannot have levels", self.topology))
+            }
+            _ => Maze::with_levels(self.width, self.height, self.depth),
+        }
+        .wrapped(self.wrap);
+        for (y, row) in self.walls.iter().enumerate() {
+            for (x, &bits) in row.iter().enumerate() {
+                let idx = maze.get_index(x, y);
+                if bits >> maze.cells[idx].slots().len() != 0 {
+                    return Err(format!("invalid wall bits {} at ({}, {})", bits, x, y));
+                }
+                for slot in maze.cells[idx].slots() {
+                    maze.cells[idx].walls[slot] = bits & (1 << slot) != 0;
+                }
+            }
+        }
+
+        for idx in 0..maze.cells.len() {
+            for slot in maze.cells[idx].slots() {
+                let Some(n_idx) = maze.neighbor_at(idx, slot) else {
+                    continue;
+                };
+                let back = maze.back_slot(idx, slot);
+                if maze.cells[idx].walls[slot] != maze.cells[n_idx].walls[back] {
+                    let (x, y) = maze.coords(idx);
+                    let (nx, ny) = maze.coords(n_idx);
+                    return Err(format!(
+                        "({}, {}) and ({}, {}) disagree about the wall between them",
+                        x, y, nx, ny
+                    ));
+                }
+            }
+        }
+
+        for (name, opening) in [("entrance", self.entrance), ("exit", self.exit)] {
+            if let Some((x, y)) = opening {
+                let on_edge =
+                    maze.contains(x, y) && maze.boundary_slot(maze.get_index(x, y)).is_some();
+                if !on_edge {
+                    return Err(format!(
+                        "{} ({}, {}) is not a cell on the edge of the maze",
+                        name, x, y
+                    ));
+                }
+            }
+        }
+        maze.entrance = self.entrance;
+        maze.exit = self.exit;
+        Ok(maze)
+    }
+
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        serde_json::to_writer(&mut *out, self)?;
+        writeln!(out)
+    }
+
+    pub fn parse(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json).map_err(|error| format!("invalid maze JSON: {}", error))
+    }
+}
+
+impl Maze {
+    /// Writes the maze in the [`json`](crate::json) format, without a seed or
+    /// algorithm.
+    pub fn write_json<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        MazeDocument::new(self).write(out)
+    }
+
+    /// Loads a maze written in the [`json`](crate::json) format.
+    pub fn from_json(json: &str) -> Result<Maze, String> {
+        MazeDocument::parse(json)?.to_maze()
+    }
+}
+
+fn one() -> usize {
+    1
+}
+
+fn is_one(depth: &usize) -> bool {
+    *depth == 1
+}
+
+fn wall_bits(walls: &[bool]) -> u8 {
+    walls
+        .iter()
+        .enumerate()
+        .filter(|(_, &wall)| wall)
+        .map(|(slot, _)| 1 << slot)
+        .sum()
+}
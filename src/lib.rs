@@ -0,0 +1,30 @@
+//! Maze generation and analysis.
+//!
+//! The [`Maze`] type holds a grid of square, hexagonal, triangular or polar
+//! [`Cell`]s (see [`Topology`]), square grids optionally joined at their
+//! edges (see [`Wrap`]) or stacked into levels (see [`Maze::with_levels`]),
+//! the functions in
+//! [`generators`] carve passages into it, [`Maze::measure_quality`] reports
+//! metrics about the result, the [`solvers`] find paths through it and
+//! [`render`] draws it as an image. [`json`] saves and loads mazes.
+
+pub mod generators;
+pub mod json;
+mod maze;
+mod parse;
+mod quality;
+pub mod render;
+pub mod solvers;
+mod topology;
+
+pub use generators::{
+    aldous_broder, binary_tree, dfs, eller, growing_tree, hunt_and_kill, kruskal, prim,
+    recursive_division, sidewinder, wilson, Bias, BinaryTree, EllerRows, GrowingTree, HuntAndKill,
+    MazeGenerator, Registry, Selection, Sidewinder,
+};
+pub use json::{MazeDocument, Metrics};
+pub use maze::{write_ascii_rows, Cell, Direction, Maze};
+pub use quality::{calculate_quality_index, MazeQuality};
+pub use render::{LineCap, PngOptions, Rgb, SvgOptions, TextStyle};
+pub use solvers::{MazeSolver, Solution, SolverRegistry};
+pub use topology::{HexDirection, Topology, Wrap};
@@ -1,377 +1,901 @@
-use clap::{value_parser, Arg, Command};
+use clap::builder::{PossibleValue, PossibleValuesParser, RangedU64ValueParser, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use mazegenerator::{
+    calculate_quality_index, write_ascii_rows, Bias, BinaryTree, EllerRows, GrowingTree, LineCap,
+    Maze, MazeDocument, PngOptions, Registry, Rgb, Selection, Sidewinder, SolverRegistry,
+    SvgOptions, TextStyle, Topology, Wrap,
+};
 use rand::prelude::*;
-use std::time::Instant;
+use rand_chacha::ChaCha8Rng;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-struct Cell {
-    x: usize,
-    y: usize,
-    visited: bool,
-    walls: [bool; 4],
-}
+fn main() {
+    let mut registry = Registry::new();
+    let algorithms: Vec<PossibleValue> = registry
+        .iter()
+        .map(|generator| PossibleValue::new(generator.name()).help(generator.description()))
+        .collect();
+    let solvers = SolverRegistry::new();
+    let solver_names: Vec<PossibleValue> = solvers
+        .iter()
+        .map(|solver| PossibleValue::new(solver.name()).help(solver.description()))
+        .collect();
+
+    let matches = Command::new("Maze Generator")
+        .version("1.0")
+        .author("Volker Schwaberow <volker@schwaberow.de>")
+        .about("Generates mazes using various algorithms")
+        .subcommand_negates_reqs(true)
+        .arg(
+            Arg::new("width")
+                .short('w')
+                .long("width")
+                .value_name("WIDTH")
+                .help("Sets the width of the maze")
+                .required_unless_present("input")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("height")
+                .short('g')
+                .long("height")
+                .value_name("HEIGHT")
+                .help("Sets the height of the maze")
+                .required_unless_present("input")
+                .value_parser(value_parser!(usize)),
+        )
+        .arg(
+            Arg::new("depth")
+                .short('d')
+                .long("depth")
+                .value_name("DEPTH")
+                .help("Sets the number of levels of a square maze, joined by stairs")
+                .default_value("1")
+                .value_parser(RangedU64ValueParser::<usize>::new().range(1..)),
+        )
+        .arg(
+            Arg::new("algorithm")
+                .short('a')
+                .long("algorithm")
+                .value_name("ALGORITHM")
+                .help("Sets the algorithm to use")
+                .required_unless_present("input")
+                .value_parser(PossibleValuesParser::new(algorithms)),
+        )
+        .arg(
+            Arg::new("topology")
+                .short('t')
+                .long("topology")
+                .value_name("TOPOLOGY")
+                .help("Sets the shape of the cells: square, hex, delta (triangles) or polar (--height rings around a center cell, --width is ignored)")
+                .default_value("square")
+                .value_parser(
+                    PossibleValuesParser::new(["square", "hex", "polar", "delta"])
+                        .map(|s| s.parse::<Topology>().unwrap()),
+                ),
+        )
+        .arg(
+            Arg::new("wrap")
+                .long("wrap")
+                .value_name("WRAP")
+                .help("Joins the edges of a square maze: horizontal (a cylinder), vertical, both (a torus) or mobius (a Möbius strip)")
+                .default_value("none")
+                .value_parser(
+                    PossibleValuesParser::new(["none", "horizontal", "vertical", "both", "mobius"])
+                        .map(|s| s.parse::<Wrap>().unwrap()),
+                ),
+        )
+        .arg(
+            Arg::new("input")
+                .short('i')
+                .long("input")
+                .value_name("FILE")
+                .help("Loads a maze saved with --format json or drawn in ASCII instead of generating one")
+                .conflicts_with_all([
+                    "width",
+                    "height",
+                    "depth",
+                    "algorithm",
+                    "topology",
+                    "wrap",
+                    "stream",
+                    "selection",
+                    "bias",
+                ])
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("seed")
+                .short('s')
+                .long("seed")
+                .value_name("SEED")
+                .help("Sets the random seed so the maze can be reproduced")
+                .value_parser(value_parser!(u64)),
+        )
+        .arg(
+            Arg::new("stream")
+                .long("stream")
+                .help("Prints rows as they are generated instead of building the whole maze (eller only)")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("entrance")
+                .long("entrance")
+                .value_name("OPENING")
+                .help("Opens the outer wall at the entrance: X,Y, random or farthest")
+                .conflicts_with("stream")
+                .value_parser(parse_opening),
+        )
+        .arg(
+            Arg::new("exit")
+                .long("exit")
+                .value_name("OPENING")
+                .help("Opens the outer wall at the exit: X,Y, random or farthest")
+                .conflicts_with("stream")
+                .value_parser(parse_opening),
+        )
+        .arg(
+            Arg::new("solve")
+                .long("solve")
+                .help("Draws the shortest path from the entrance to the exit (top-left to bottom-right cell by default)")
+                .conflicts_with("stream")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("solver")
+                .long("solver")
+                .value_name("SOLVER")
+                .help("Sets the algorithm used to solve the maze and reports its search statistics (implies --solve)")
+                .conflicts_with("stream")
+                .value_parser(PossibleValuesParser::new(solver_names)),
+        )
+        .arg(
+            Arg::new("trace")
+                .long("trace")
+                .help("Prints the cells the solver visited, in order")
+                .conflicts_with("stream")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("format")
+                .short('f')
+                .long("format")
+                .value_name("FORMAT")
+                .help("Sets the output format of the maze")
+                .default_value("ascii")
+                .value_parser(PossibleValuesParser::new(["ascii", "svg", "png", "json"])),
+        )
+        .arg(
+            Arg::new("style")
+                .long("style")
+                .value_name("STYLE")
+                .help("Sets the characters used by --format ascii: ascii (+---+), unicode (box drawing) or blocks (one character per cell)")
+                .default_value("ascii")
+                .conflicts_with("stream")
+                .value_parser(
+                    PossibleValuesParser::new(["ascii", "unicode", "blocks"])
+                        .map(|s| s.parse::<TextStyle>().unwrap()),
+                ),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FILE")
+                .help("Writes the maze to FILE instead of stdout")
+                .value_parser(value_parser!(PathBuf)),
+        )
+        .arg(
+            Arg::new("cell-size")
+                .long("cell-size")
+                .value_name("PIXELS")
+                .help("Sets the width and height of a cell in SVG and PNG output [default: 20]")
+                .value_parser(parse_length),
+        )
+        .arg(
+            Arg::new("wall-thickness")
+                .long("wall-thickness")
+                .value_name("PIXELS")
+                .help("Sets the stroke width of walls and of the solution in SVG and PNG output [default: 2]")
+                .value_parser(parse_length),
+        )
+        .arg(
+            Arg::new("margin")
+                .long("margin")
+                .value_name("PIXELS")
+                .help("Sets the space around the maze in SVG and PNG output [default: 10]")
+                .value_parser(parse_length),
+        )
+        .arg(
+            Arg::new("line-cap")
+                .long("line-cap")
+                .value_name("CAP")
+                .help("Sets how wall ends are drawn in the SVG output [default: square]")
+                .value_parser(
+                    PossibleValuesParser::new(["butt", "round", "square"])
+                        .map(|s| s.parse::<LineCap>().unwrap()),
+                ),
+        )
+        .arg(
+            Arg::new("background")
+                .long("background")
+                .value_name("COLOR")
+                .help("Sets the background color of SVG and PNG output [default: white]"),
+        )
+        .arg(
+            Arg::new("wall-color")
+                .long("wall-color")
+                .value_name("COLOR")
+                .help("Sets the wall color of SVG and PNG output [default: black]"),
+        )
+        .arg(
+            Arg::new("path-color")
+                .long("path-color")
+                .value_name("COLOR")
+                .help("Sets the color of the solution and the entrance and exit markers in SVG and PNG output [default: red]"),
+        )
+        .arg(
+            Arg::new("heat-map")
+                .long("heat-map")
+                .help("Colors the passages of PNG output by their distance from the entrance")
+                .action(ArgAction::SetTrue),
+        )
+        .arg(
+            Arg::new("selection")
+                .long("selection")
+                .value_name("SELECTION")
+                .help("Sets how growing-tree picks the next cell: newest, oldest, random, middle or mix:newest=75,random=25")
+                .value_parser(|s: &str| s.parse::<Selection>()),
+        )
+        .arg(
+            Arg::new("bias")
+                .long("bias")
+                .value_name("BIAS")
+                .help("Sets the corner binary-tree and sidewinder lean towards")
+                .value_parser(
+                    PossibleValuesParser::new([
+                        "north-east",
+                        "north-west",
+                        "south-east",
+                        "south-west",
+                    ])
+                        .map(|s| s.parse::<Bias>().unwrap()),
+                ),
+        )
+        .subcommand(
+            Command::new("benchmark")
+                .about("Generates mazes with every algorithm and compares time and quality")
+                .arg(
+                    Arg::new("width")
+                        .short('w')
+                        .long("width")
+                        .value_name("WIDTH")
+                        .help("Sets the width of the mazes")
+                        .default_value("20")
+                        .value_parser(value_parser!(usize)),
+                )
+                .arg(
+                    Arg::new("height")
+                        .short('g')
+                        .long("height")
+                        .value_name("HEIGHT")
+                        .help("Sets the height of the mazes")
+                        .default_value("20")
+                        .value_parser(value_parser!(usize)),
+                )
+                .arg(
+                    Arg::new("topology")
+                        .short('t')
+                        .long("topology")
+                        .value_name("TOPOLOGY")
+                        .help("Sets the shape of the cells; algorithms that do not support it are skipped")
+                        .default_value("square")
+                        .value_parser(
+                            PossibleValuesParser::new(["square", "hex", "polar", "delta"])
+                                .map(|s| s.parse::<Topology>().unwrap()),
+                        ),
+                )
+                .arg(
+                    Arg::new("runs")
+                        .short('r')
+                        .long("runs")
+                        .value_name("RUNS")
+                        .help("Sets how many mazes each algorithm generates")
+                        .default_value("5")
+                        .value_parser(value_parser!(u32).range(1..)),
+                )
+                .arg(
+                    Arg::new("seed")
+                        .short('s')
+                        .long("seed")
+                        .value_name("SEED")
+                        .help("Sets the random seed of the first run")
+                        .value_parser(value_parser!(u64)),
+                ),
+        )
+        .get_matches();
 
-struct Maze {
-    width: usize,
-    height: usize,
-    cells: Vec<Cell>,
+    match matches.subcommand() {
+        Some(("benchmark", sub_matches)) => benchmark(&registry, sub_matches),
+        _ => generate(&mut registry, &solvers, &matches),
+    }
 }
 
-struct MazeQuality {
-    dead_ends: usize,
-    longest_path: usize,
-    avg_path_length: f64,
-    branching_factor: f64,
+/// Where `--entrance` or `--exit` opens the outer wall.
+#[derive(Clone, Copy)]
+enum Opening {
+    At(usize, usize),
+    Random,
+    /// At the end of the longest path to the other opening, or of the
+    /// longest path between any two boundary cells.
+    Farthest,
 }
 
-impl Maze {
-    fn new(width: usize, height: usize) -> Self {
-        let cells = (0..height)
-            .flat_map(|y| {
-                (0..width).map(move |x| Cell {
-                    x,
-                    y,
-                    visited: false,
-                    walls: [true, true, true, true],
-                })
-            })
-            .collect();
-
-        Maze {
-            width,
-            height,
-            cells,
+fn parse_opening(s: &str) -> Result<Opening, String> {
+    match s {
+        "random" => Ok(Opening::Random),
+        "farthest" => Ok(Opening::Farthest),
+        _ => {
+            let coordinate = |value: &str| {
+                value
+                    .trim()
+                    .parse::<usize>()
+                    .map_err(|_| format!("expected X,Y, random or farthest, got '{}'", s))
+            };
+            let (x, y) = s
+                .split_once(',')
+                .ok_or_else(|| format!("expected X,Y, random or farthest, got '{}'", s))?;
+            Ok(Opening::At(coordinate(x)?, coordinate(y)?))
         }
     }
+}
 
-    fn get_index(&self, x: usize, y: usize) -> usize {
-        y * self.width + x
+fn place_openings<R: Rng>(
+    maze: &mut Maze,
+    entrance: Option<Opening>,
+    exit: Option<Opening>,
+    rng: &mut R,
+) -> Result<(), String> {
+    if maze.wrap() == Wrap::Both && (entrance.is_some() || exit.is_some()) {
+        return Err("a maze wrapped on both axes has no outer edge to open".to_string());
     }
-
-    fn remove_wall(&mut self, x1: usize, y1: usize, x2: usize, y2: usize) {
-        let idx1 = self.get_index(x1, y1);
-        let idx2 = self.get_index(x2, y2);
-
-        if y1 < y2 {
-            self.cells[idx1].walls[2] = false;
-            self.cells[idx2].walls[0] = false;
-        } else if y1 > y2 {
-            self.cells[idx1].walls[0] = false;
-            self.cells[idx2].walls[2] = false;
-        } else if x1 < x2 {
-            self.cells[idx1].walls[1] = false;
-            self.cells[idx2].walls[3] = false;
-        } else {
-            self.cells[idx1].walls[3] = false;
-            self.cells[idx2].walls[1] = false;
+    let mut resolve = |opening: Option<Opening>| match opening {
+        Some(Opening::At(x, y)) => Some((x, y)),
+        Some(Opening::Random) => maze.boundary_cells().choose(rng),
+        Some(Opening::Farthest) | None => None,
+    };
+    let mut entrance_cell = resolve(entrance);
+    let mut exit_cell = resolve(exit);
+
+    // Without a cell to measure from, fall back on a cell that setting the
+    // opening rejects with the usual error.
+    match (entrance, exit, entrance_cell, exit_cell) {
+        (Some(Opening::Farthest), _, _, Some(other)) => {
+            entrance_cell = Some(maze.farthest_boundary_cell(other).unwrap_or(other))
         }
-    }
-    fn print(&self) {
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let idx = self.get_index(x, y);
-                print!(
-                    "{}{}{}",
-                    if x == 0 { "+" } else { "" },
-                    if self.cells[idx].walls[0] {
-                        "---"
-                    } else {
-                        "   "
-                    },
-                    "+"
-                );
-            }
-            println!();
-
-            for x in 0..self.width {
-                let idx = self.get_index(x, y);
-                print!(
-                    "{}{}",
-                    if self.cells[idx].walls[3] { "|" } else { " " },
-                    "   "
-                );
-            }
-            println!("|");
+        (_, Some(Opening::Farthest), Some(other), _) => {
+            exit_cell = Some(maze.farthest_boundary_cell(other).unwrap_or(other))
         }
-
-        for _x in 0..self.width {
-            print!("+---");
+        (Some(Opening::Farthest), _, _, None) | (_, Some(Opening::Farthest), None, _) => {
+            let (first, second) = maze.farthest_boundary_pair().unwrap_or(((0, 0), (0, 0)));
+            entrance_cell = entrance.map(|_| first);
+            exit_cell = exit.map(|_| second);
         }
-        println!("+");
+        _ => {}
     }
 
-    fn measure_quality(&self) -> MazeQuality {
-        let dead_ends = self.count_dead_ends();
-        let (longest_path, total_path_length, total_paths) = self.measure_paths();
-        let branching_factor = self.calculate_branching_factor();
-
-        MazeQuality {
-            dead_ends,
-            longest_path,
-            avg_path_length: total_path_length as f64 / total_paths as f64,
-            branching_factor,
-        }
+    if let Some((x, y)) = entrance_cell {
+        maze.set_entrance(x, y)?;
     }
+    if let Some((x, y)) = exit_cell {
+        maze.set_exit(x, y)?;
+    }
+    Ok(())
+}
 
-    fn count_dead_ends(&self) -> usize {
-        self.cells
-            .iter()
-            .filter(|&cell| cell.walls.iter().filter(|&&wall| wall).count() == 3)
-            .count()
+fn parse_length(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(length) if length.is_finite() && length >= 0.0 => Ok(length),
+        _ => Err(format!(
+            "expected a non-negative number of pixels, got '{}'",
+            s
+        )),
     }
+}
 
-    fn measure_paths(&self) -> (usize, usize, usize) {
-        let mut longest_path = 0;
-        let mut total_path_length = 0;
-        let mut total_paths = 0;
-
-        for start_cell in &self.cells {
-            let (path_length, path_count) = {
-                let start_x = start_cell.x;
-                let start_y = start_cell.y;
-                self.longest_path_from(start_x, start_y)
-            };
-            longest_path = longest_path.max(path_length);
-            total_path_length += path_length;
-            total_paths += path_count;
-        }
+/// The `--format` of the maze, with the options of each format.
+enum Render {
+    Text(TextStyle),
+    Svg(SvgOptions),
+    Png(PngOptions),
+    /// The JSON document records where the maze came from.
+    Json {
+        algorithm: Option<String>,
+        seed: Option<u64>,
+    },
+}
 
-        (longest_path, total_path_length, total_paths)
-    }
-
-    fn longest_path_from(&self, start_x: usize, start_y: usize) -> (usize, usize) {
-        let mut visited = vec![vec![false; self.width]; self.height];
-        self.dfs_longest_path(start_x, start_y, &mut visited, 0)
-    }
-
-    fn dfs_longest_path(
-        &self,
-        x: usize,
-        y: usize,
-        visited: &mut Vec<Vec<bool>>,
-        length: usize,
-    ) -> (usize, usize) {
-        visited[y][x] = true;
-        let mut max_length = length;
-        let mut path_count = 0;
-
-        let directions = [(0, -1), (1, 0), (0, 1), (-1, 0)];
-        for (i, &(dx, dy)) in directions.iter().enumerate() {
-            let nx = x as i32 + dx;
-            let ny = y as i32 + dy;
-            if nx >= 0 && nx < self.width as i32 && ny >= 0 && ny < self.height as i32 {
-                let nx = nx as usize;
-                let ny = ny as usize;
-                if !self.cells[self.get_index(x, y)].walls[i] && !visited[ny][nx] {
-                    let (sub_length, sub_count) =
-                        self.dfs_longest_path(nx, ny, visited, length + 1);
-                    max_length = max_length.max(sub_length);
-                    path_count += sub_count;
-                }
-            }
-        }
+fn render_from(matches: &ArgMatches, algorithm: Option<String>, seed: Option<u64>) -> Render {
+    match matches.get_one::<String>("format").unwrap().as_str() {
+        "svg" => Render::Svg(svg_options(matches)),
+        "png" => Render::Png(png_options(matches)),
+        "json" => Render::Json { algorithm, seed },
+        _ => Render::Text(*matches.get_one::<TextStyle>("style").unwrap()),
+    }
+}
 
-        visited[y][x] = false;
-        (max_length, if path_count == 0 { 1 } else { path_count })
+fn svg_options(matches: &ArgMatches) -> SvgOptions {
+    let mut options = SvgOptions::default();
+    if let Some(&cell_size) = matches.get_one::<f64>("cell-size") {
+        options.cell_size = cell_size;
+    }
+    if let Some(&wall_thickness) = matches.get_one::<f64>("wall-thickness") {
+        options.wall_thickness = wall_thickness;
+    }
+    if let Some(&margin) = matches.get_one::<f64>("margin") {
+        options.margin = margin;
+    }
+    if let Some(&line_cap) = matches.get_one::<LineCap>("line-cap") {
+        options.line_cap = line_cap;
+    }
+    if let Some(background) = matches.get_one::<String>("background") {
+        options.background = background.clone();
+    }
+    if let Some(wall_color) = matches.get_one::<String>("wall-color") {
+        options.wall_color = wall_color.clone();
+    }
+    if let Some(path_color) = matches.get_one::<String>("path-color") {
+        options.path_color = path_color.clone();
     }
+    options
+}
 
-    fn calculate_branching_factor(&self) -> f64 {
-        let total_branches: usize = self
-            .cells
-            .iter()
-            .map(|cell| 4 - cell.walls.iter().filter(|&&wall| wall).count())
-            .sum();
+fn png_options(matches: &ArgMatches) -> PngOptions {
+    let color = |id: &str, default: Rgb| match matches.get_one::<String>(id) {
+        Some(value) => value.parse::<Rgb>().unwrap_or_else(|message| {
+            clap::Error::raw(
+                ErrorKind::ValueValidation,
+                format!("invalid value '{}' for '--{}': {}\n", value, id, message),
+            )
+            .exit()
+        }),
+        None => default,
+    };
+    let pixels = |id: &str, default: u32| match matches.get_one::<f64>(id) {
+        Some(&length) if length.round() > f64::from(u32::MAX) => clap::Error::raw(
+            ErrorKind::ValueValidation,
+            format!(
+                "invalid value '{}' for '--{}': PNG output takes at most {} pixels\n",
+                length,
+                id,
+                u32::MAX
+            ),
+        )
+        .exit(),
+        Some(&length) => length.round() as u32,
+        None => default,
+    };
+
+    let defaults = PngOptions::default();
+    PngOptions {
+        cell_size: pixels("cell-size", defaults.cell_size),
+        wall_thickness: pixels("wall-thickness", defaults.wall_thickness),
+        margin: pixels("margin", defaults.margin),
+        background: color("background", defaults.background),
+        wall_color: color("wall-color", defaults.wall_color),
+        path_color: color("path-color", defaults.path_color),
+        heat_map: matches.get_flag("heat-map"),
+    }
+}
 
-        total_branches as f64 / (self.width * self.height) as f64
+/// Where the maze and the report around it are written. The report shares
+/// stdout with a text maze but moves to stderr when an image goes to stdout.
+fn outputs(matches: &ArgMatches, render: &Render) -> (Box<dyn Write>, Box<dyn Write>) {
+    let image = !matches!(render, Render::Text(_));
+    match matches.get_one::<PathBuf>("output") {
+        Some(path) => match File::create(path) {
+            Ok(file) => (Box::new(BufWriter::new(file)), Box::new(io::stdout())),
+            Err(error) => clap::Error::raw(
+                ErrorKind::Io,
+                format!("cannot create {}: {}\n", path.display(), error),
+            )
+            .exit(),
+        },
+        None if image => (Box::new(io::stdout()), Box::new(io::stderr())),
+        None => (Box::new(io::stdout()), Box::new(io::stdout())),
     }
 }
 
-fn kruskal(maze: &mut Maze) {
-    let mut rng = thread_rng();
-    let mut sets: Vec<usize> = (0..maze.width * maze.height).collect();
-    let mut walls: Vec<(usize, usize, usize, usize)> = Vec::new();
-
-    for y in 0..maze.height {
-        for x in 0..maze.width {
-            if x < maze.width - 1 {
-                walls.push((x, y, x + 1, y));
-            }
-            if y < maze.height - 1 {
-                walls.push((x, y, x, y + 1));
-            }
-        }
+fn seed_from(matches: &ArgMatches) -> u64 {
+    matches
+        .get_one::<u64>("seed")
+        .copied()
+        .unwrap_or_else(|| thread_rng().gen())
+}
+
+fn require_algorithm(algorithm: &str, supported: &[&str], option: &str) {
+    if !supported.contains(&algorithm) {
+        clap::Error::raw(
+            ErrorKind::ArgumentConflict,
+            format!(
+                "{} is only supported by the {} algorithm{}\n",
+                option,
+                supported.join(" and "),
+                if supported.len() > 1 { "s" } else { "" }
+            ),
+        )
+        .exit();
     }
+}
 
-    walls.shuffle(&mut rng);
+fn generate(registry: &mut Registry, solvers: &SolverRegistry, matches: &ArgMatches) {
+    if let Some(path) = matches.get_one::<PathBuf>("input") {
+        let (mut maze, algorithm, seed) = load(path).unwrap_or_else(|message| {
+            clap::Error::raw(
+                ErrorKind::InvalidValue,
+                format!("cannot load {}: {}\n", path.display(), message),
+            )
+            .exit()
+        });
+        let mut rng = ChaCha8Rng::seed_from_u64(seed_from(matches));
+        open_maze(&mut maze, matches, &mut rng);
+        let header = format!("Maze loaded from {}:", path.display());
+        let render = render_from(matches, algorithm, seed);
+        report(&maze, solvers, matches, &header, &render, None);
+        return;
+    }
 
-    for (x1, y1, x2, y2) in walls {
-        let idx1 = maze.get_index(x1, y1);
-        let idx2 = maze.get_index(x2, y2);
+    let width = *matches.get_one::<usize>("width").unwrap();
+    let height = *matches.get_one::<usize>("height").unwrap();
+    let depth = *matches.get_one::<usize>("depth").unwrap();
+    let algorithm = matches.get_one::<String>("algorithm").unwrap();
+    let topology = *matches.get_one::<Topology>("topology").unwrap();
+    let wrap = *matches.get_one::<Wrap>("wrap").unwrap();
+    let seed = seed_from(matches);
+
+    if !wrap.is_none() && topology != Topology::Square {
+        clap::Error::raw(
+            ErrorKind::ArgumentConflict,
+            "--wrap only supports square mazes\n",
+        )
+        .exit();
+    }
+    if depth != 1 && topology != Topology::Square {
+        clap::Error::raw(
+            ErrorKind::ArgumentConflict,
+            "--depth only supports square mazes\n",
+        )
+        .exit();
+    }
+    check_size(topology, width, height);
+
+    if matches.get_flag("stream") {
+        require_algorithm(algorithm, &["eller"], "--stream");
+        if topology != Topology::Square || !wrap.is_none() || depth != 1 {
+            clap::Error::raw(
+                ErrorKind::ArgumentConflict,
+                "--stream only supports square mazes without --wrap or --depth\n",
+            )
+            .exit();
+        }
+        if matches.get_one::<String>("format").unwrap() != "ascii" || matches.contains_id("output")
+        {
+            clap::Error::raw(
+                ErrorKind::ArgumentConflict,
+                "--stream only writes ASCII mazes to stdout\n",
+            )
+            .exit();
+        }
+        stream(width, height, seed);
+        return;
+    }
 
-        let set1 = find(&mut sets, idx1);
-        let set2 = find(&mut sets, idx2);
+    if let Some(selection) = matches.get_one::<Selection>("selection") {
+        require_algorithm(algorithm, &["growing-tree"], "--selection");
+        registry.register(GrowingTree::new(selection.clone()));
+    }
 
-        if set1 != set2 {
-            maze.remove_wall(x1, y1, x2, y2);
-            union(&mut sets, set1, set2);
+    if let Some(&bias) = matches.get_one::<Bias>("bias") {
+        require_algorithm(algorithm, &["binary-tree", "sidewinder"], "--bias");
+        match algorithm.as_str() {
+            "binary-tree" => registry.register(BinaryTree::new(bias)),
+            _ => registry.register(Sidewinder::new(bias)),
         }
     }
-}
 
-fn find(sets: &mut Vec<usize>, x: usize) -> usize {
-    if sets[x] != x {
-        sets[x] = find(sets, sets[x]);
+    let generator = registry.get(algorithm).unwrap();
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut maze = match depth {
+        1 => Maze::with_topology(topology, width, height),
+        _ => Maze::with_levels(width, height, depth),
+    }
+    .wrapped(wrap);
+    if !generator.supports(&maze) {
+        let shape = if depth != 1 {
+            "multi-level".to_string()
+        } else if !wrap.is_none() {
+            "wrapped".to_string()
+        } else {
+            topology.to_string()
+        };
+        clap::Error::raw(
+            ErrorKind::ArgumentConflict,
+            format!("{} does not support {} mazes\n", algorithm, shape),
+        )
+        .exit();
     }
-    sets[x]
+
+    let start = Instant::now();
+    generator.generate(&mut maze, &mut rng);
+    let duration = start.elapsed();
+
+    open_maze(&mut maze, matches, &mut rng);
+    let header = format!(
+        "Maze generated using {} algorithm (seed {}):",
+        algorithm, seed
+    );
+    let render = render_from(matches, Some(algorithm.clone()), Some(seed));
+    report(&maze, solvers, matches, &header, &render, Some(duration));
 }
 
-fn union(sets: &mut Vec<usize>, x: usize, y: usize) {
-    let root_x = find(sets, x);
-    let root_y = find(sets, y);
-    sets[root_x] = root_y;
+/// Reads a maze saved as JSON or drawn as text, with the algorithm and seed
+/// it was generated with when the file records them.
+fn load(path: &Path) -> Result<(Maze, Option<String>, Option<u64>), String> {
+    let text = fs::read_to_string(path).map_err(|error| error.to_string())?;
+    if text.trim_start().starts_with('{') {
+        let document = MazeDocument::parse(&text)?;
+        Ok((document.to_maze()?, document.algorithm, document.seed))
+    } else {
+        Ok((Maze::from_ascii(&text)?, None, None))
+    }
 }
 
-fn prim(maze: &mut Maze) {
-    let mut rng = thread_rng();
-    let start_x = rng.gen_range(0..maze.width);
-    let start_y = rng.gen_range(0..maze.height);
-    let mut frontier = vec![(start_x, start_y)];
-    let maze_index = maze.get_index(start_x, start_y);
-    maze.cells[maze_index].visited = true;
-
-    while !frontier.is_empty() {
-        let idx = rng.gen_range(0..frontier.len());
-        let (x, y) = frontier.swap_remove(idx);
-
-        let neighbors = [
-            (x, y.wrapping_sub(1)),
-            (x + 1, y),
-            (x, y + 1),
-            (x.wrapping_sub(1), y),
-        ];
-
-        for &(nx, ny) in &neighbors {
-            if nx < maze.width && ny < maze.height {
-                let n_idx = maze.get_index(nx, ny);
-                let is_unvisited = !maze.cells[n_idx].visited;
-                if is_unvisited {
-                    maze.remove_wall(x, y, nx, ny);
-                    maze.cells[n_idx].visited = true;
-                    frontier.push((nx, ny));
-                }
-            }
-        }
+fn open_maze(maze: &mut Maze, matches: &ArgMatches, rng: &mut ChaCha8Rng) {
+    if let Err(message) = place_openings(
+        maze,
+        matches.get_one::<Opening>("entrance").copied(),
+        matches.get_one::<Opening>("exit").copied(),
+        rng,
+    ) {
+        clap::Error::raw(ErrorKind::ValueValidation, format!("{}\n", message)).exit();
     }
 }
 
-fn dfs(maze: &mut Maze) {
-    let mut rng = thread_rng();
-    let mut stack = vec![(0, 0)];
-    maze.cells[0].visited = true;
-
-    while let Some(&(x, y)) = stack.last() {
-        let mut neighbors = Vec::new();
-        let directions = [(0, -1), (1, 0), (0, 1), (-1, 0)];
-
-        for (dx, dy) in directions.iter() {
-            let nx = x as i32 + dx;
-            let ny = y as i32 + dy;
-            if nx >= 0 && nx < maze.width as i32 && ny >= 0 && ny < maze.height as i32 {
-                let n_idx = maze.get_index(nx as usize, ny as usize);
-                if !maze.cells[n_idx].visited {
-                    neighbors.push((nx as usize, ny as usize));
-                }
-            }
+/// Writes the header, the maze and everything known about it. `duration` is
+/// the time it took to generate the maze, if it was generated.
+fn report(
+    maze: &Maze,
+    solvers: &SolverRegistry,
+    matches: &ArgMatches,
+    header: &str,
+    render: &Render,
+    duration: Option<Duration>,
+) {
+    require_square(maze, render);
+    let (mut out, mut report) = outputs(matches, render);
+    let written = writeln!(report, "{}", header)
+        .and_then(|()| write_maze(maze, solvers, matches, render, &mut out, &mut report))
+        .and_then(|()| out.flush());
+    // A maze that cannot be drawn, such as an image too large for PNG, is
+    // reported without leaving an empty or partial file behind.
+    if let Err(error) = written {
+        if error.kind() != io::ErrorKind::InvalidInput {
+            panic!("failed writing the maze: {}", error);
         }
-
-        if !neighbors.is_empty() {
-            let &(nx, ny) = neighbors.choose(&mut rng).unwrap();
-            maze.remove_wall(x, y, nx, ny);
-            let maze_index = maze.get_index(nx, ny);
-            maze.cells[maze_index].visited = true;
-            stack.push((nx, ny));
-        } else {
-            stack.pop();
+        drop(out);
+        if let Some(path) = matches.get_one::<PathBuf>("output") {
+            let _ = fs::remove_file(path);
         }
+        clap::Error::raw(ErrorKind::ValueValidation, format!("{}\n", error)).exit();
     }
+    write_quality(maze, duration, &mut report).expect("failed writing the maze");
 }
 
-fn calculate_quality_index(quality: &MazeQuality, maze_size: usize) -> f64 {
-    let dead_end_ratio = quality.dead_ends as f64 / maze_size as f64;
-    let path_length_ratio = quality.longest_path as f64 / maze_size as f64;
-    let normalized_avg_path = quality.avg_path_length / maze_size as f64;
-
-    let w_dead_ends = 0.25;
-    let w_longest_path = 0.3;
-    let w_avg_path = 0.25;
-    let w_branching = 0.2;
-
-    (1.0 - dead_end_ratio) * w_dead_ends
-        + path_length_ratio * w_longest_path
-        + normalized_avg_path * w_avg_path
-        + quality.branching_factor * w_branching
+/// Exits with an error when a `width` by `height` grid of `topology` cells
+/// would fall apart.
+fn check_size(topology: Topology, width: usize, height: usize) {
+    if let Err(message) = topology.check_size(width, height) {
+        clap::Error::raw(ErrorKind::ValueValidation, format!("{}\n", message)).exit();
+    }
 }
 
-fn main() {
-    let matches = Command::new("Maze Generator")
-        .version("1.0")
-        .author("Volker Schwaberow <volker@schwaberow.de>")
-        .about("Generates mazes using various algorithms")
-        .arg(
-            Arg::new("width")
-                .short('w')
-                .long("width")
-                .value_name("WIDTH")
-                .help("Sets the width of the maze")
-                .required(true)
-                .value_parser(value_parser!(usize)),
+/// Exits with an error when `render` can only draw a single level of square
+/// cells and `maze` is something else.
+fn require_square(maze: &Maze, render: &Render) {
+    let output = match render {
+        Render::Png(_) => "--format png",
+        Render::Text(TextStyle::Unicode) => "--style unicode",
+        Render::Text(TextStyle::Blocks) => "--style blocks",
+        _ => return,
+    };
+    if maze.topology() != Topology::Square {
+        clap::Error::raw(
+            ErrorKind::ArgumentConflict,
+            format!("{} only supports square mazes\n", output),
         )
-        .arg(
-            Arg::new("height")
-                .short('g')
-                .long("height")
-                .value_name("HEIGHT")
-                .help("Sets the height of the maze")
-                .required(true)
-                .value_parser(value_parser!(usize)),
-        )
-        .arg(
-            Arg::new("algorithm")
-                .short('a')
-                .long("algorithm")
-                .value_name("ALGORITHM")
-                .help("Sets the algorithm to use (kruskal, prim, or dfs)")
-                .required(true)
-                .value_parser(["kruskal", "prim", "dfs"]),
+        .exit();
+    }
+    if maze.depth() > 1 {
+        clap::Error::raw(
+            ErrorKind::ArgumentConflict,
+            format!("{} only supports mazes with a single level\n", output),
         )
-        .get_matches();
+        .exit();
+    }
+}
 
-    let width = *matches.get_one::<usize>("width").unwrap();
-    let height = *matches.get_one::<usize>("height").unwrap();
-    let algorithm = matches.get_one::<String>("algorithm").unwrap();
+/// Writes the maze in the chosen format to `out`, solving it first if asked
+/// to, and the solver statistics and openings to `report`.
+fn write_maze(
+    maze: &Maze,
+    solvers: &SolverRegistry,
+    matches: &ArgMatches,
+    render: &Render,
+    mut out: &mut dyn Write,
+    report: &mut dyn Write,
+) -> io::Result<()> {
+    let solver_name = matches.get_one::<String>("solver");
+    let solution =
+        if matches.get_flag("solve") || solver_name.is_some() || matches.get_flag("trace") {
+            let solver = solvers.get(solver_name.map_or("bfs", |name| name)).unwrap();
+            let start = maze.entrance().unwrap_or((0, 0));
+            let last = maze.cells().last().map(|cell| (cell.x(), cell.y()));
+            let goal = maze.exit().or(last).unwrap_or((0, 0));
+            let solve_start = Instant::now();
+            let solution = solver.solve(maze, start, goal);
+            Some((solver, solution, solve_start.elapsed(), start, goal))
+        } else {
+            None
+        };
+    let path = solution
+        .as_ref()
+        .and_then(|(_, solution, ..)| solution.path.as_deref());
+
+    match render {
+        Render::Text(style) => maze.write_text(&mut out, *style, path)?,
+        Render::Svg(options) => maze.write_svg(&mut out, options, path)?,
+        Render::Png(options) => maze.write_png(&mut out, options, path)?,
+        Render::Json { algorithm, seed } => {
+            let mut document = MazeDocument::new(maze);
+            document.algorithm = algorithm.clone();
+            document.seed = *seed;
+            document.write(&mut out)?
+        }
+    }
 
-    let mut maze = Maze::new(width, height);
+    if let Some((solver, solution, solve_duration, start, goal)) = solution {
+        match &solution.path {
+            Some(path) => writeln!(report, "Solution length: {}", path.len() - 1)?,
+            None => writeln!(report, "No solution found")?,
+        }
+        match solution.steps {
+            Some(steps) => writeln!(
+                report,
+                "Solver: {}, steps: {}, optimal path: {}, time: {:?}",
+                solver.name(),
+                steps,
+                maze.solve(start, goal)
+                    .map_or("none".to_string(), |path| (path.len() - 1).to_string()),
+                solve_duration
+            )?,
+            None => writeln!(
+                report,
+                "Solver: {}, nodes expanded: {}, frontier peak: {}, time: {:?}",
+                solver.name(),
+                solution.expanded(),
+                solution.frontier_peak,
+                solve_duration
+            )?,
+        }
+        if matches.get_flag("trace") {
+            let cells: Vec<String> = solution
+                .visited
+                .iter()
+                .map(|(x, y)| format!("({}, {})", x, y))
+                .collect();
+            writeln!(report, "Visited: {}", cells.join(" -> "))?;
+        }
+    }
+    if let Some((x, y)) = maze.entrance() {
+        writeln!(report, "Entrance: ({}, {})", x, y)?;
+    }
+    if let Some((x, y)) = maze.exit() {
+        writeln!(report, "Exit: ({}, {})", x, y)?;
+    }
+    Ok(())
+}
 
-    let start = Instant::now();
+fn write_quality(
+    maze: &Maze,
+    duration: Option<Duration>,
+    report: &mut dyn Write,
+) -> io::Result<()> {
+    let quality = maze.measure_quality();
+    let quality_index = calculate_quality_index(&quality, maze.cells().len());
 
-    match algorithm.as_str() {
-        "kruskal" => kruskal(&mut maze),
-        "prim" => prim(&mut maze),
-        "dfs" => dfs(&mut maze),
-        _ => unreachable!(),
+    if let Some(duration) = duration {
+        writeln!(report, "Time taken: {:?}", duration)?;
     }
+    writeln!(report, "\nMaze Quality Metrics:")?;
+    writeln!(report, "Dead ends: {}", quality.dead_ends)?;
+    writeln!(report, "Longest path: {}", quality.longest_path)?;
+    writeln!(
+        report,
+        "Average path length: {:.2}",
+        quality.avg_path_length
+    )?;
+    writeln!(report, "Branching factor: {:.2}", quality.branching_factor)?;
+    writeln!(report, "Quality Index: {:.4}", quality_index)
+}
 
-    let duration = start.elapsed();
+fn stream(width: usize, height: usize, seed: u64) {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
 
-    println!("Maze generated using {} algorithm:", algorithm);
-    maze.print();
-    println!("Time taken: {:?}", duration);
+    println!("Maze generated using eller algorithm (seed {}):", seed);
+    let start = Instant::now();
+    let mut out = BufWriter::new(io::stdout().lock());
+    write_ascii_rows(&mut out, width, EllerRows::new(width, height, &mut rng))
+        .and_then(|()| out.flush())
+        .expect("failed printing to stdout");
+    drop(out);
+    println!("Time taken: {:?}", start.elapsed());
+}
 
-    let quality = maze.measure_quality();
-    let quality_index = calculate_quality_index(&quality, width * height);
-
-    println!("\nMaze Quality Metrics:");
-    println!("Dead ends: {}", quality.dead_ends);
-    println!("Longest path: {}", quality.longest_path);
-    println!("Average path length: {:.2}", quality.avg_path_length);
-    println!("Branching factor: {:.2}", quality.branching_factor);
-    println!("Quality Index: {:.4}", quality_index);
+fn benchmark(registry: &Registry, matches: &ArgMatches) {
+    let width = *matches.get_one::<usize>("width").unwrap();
+    let height = *matches.get_one::<usize>("height").unwrap();
+    let runs = *matches.get_one::<u32>("runs").unwrap();
+    let topology = *matches.get_one::<Topology>("topology").unwrap();
+    let seed = seed_from(matches);
+    check_size(topology, width, height);
+
+    println!(
+        "Benchmarking {}x{} {} mazes, {} runs per algorithm (seed {}):\n",
+        width, height, topology, runs, seed
+    );
+    println!(
+        "{:<20} {:>12} {:>10} {:>13} {:>10} {:>10}",
+        "Algorithm", "Avg time", "Dead ends", "Longest path", "Branching", "Quality"
+    );
+
+    for generator in registry
+        .iter()
+        .filter(|generator| generator.supports(&Maze::with_topology(topology, 0, 0)))
+    {
+        let mut total_time = Duration::ZERO;
+        let mut dead_ends = 0;
+        let mut longest_path = 0;
+        let mut branching_factor = 0.0;
+        let mut quality_index = 0.0;
+
+        for run in 0..runs {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed.wrapping_add(u64::from(run)));
+            let mut maze = Maze::with_topology(topology, width, height);
+
+            let start = Instant::now();
+            generator.generate(&mut maze, &mut rng);
+            total_time += start.elapsed();
+
+            let quality = maze.measure_quality();
+            quality_index += calculate_quality_index(&quality, maze.cells().len());
+            dead_ends += quality.dead_ends;
+            longest_path += quality.longest_path;
+            branching_factor += quality.branching_factor;
+        }
+
+        let runs_f = f64::from(runs);
+        println!(
+            "{:<20} {:>12.2?} {:>10.1} {:>13.1} {:>10.2} {:>10.4}",
+            generator.name(),
+            total_time / runs,
+            dead_ends as f64 / runs_f,
+            longest_path as f64 / runs_f,
+            branching_factor / runs_f,
+            quality_index / runs_f
+        );
+    }
 }
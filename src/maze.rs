@@ -0,0 +1,695 @@
+use crate::topology::{polar_rings, polar_slots, Topology, Wrap, DOWN, MAX_SLOTS, UP};
+use rand::Rng;
+use std::io::{self, Write};
+use std::ops::Range;
+
+/// Distance marker for cells a search has not reached.
+pub(crate) const UNREACHED: usize = usize::MAX;
+
+/// One of the four sides of a square cell.
+///
+/// The discriminant is the index of the matching entry in [`Cell::walls`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North = 0,
+    East = 1,
+    South = 2,
+    West = 3,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn opposite(self) -> Direction {
+        Direction::ALL[(self.index() + 2) % 4]
+    }
+
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::North => (0, -1),
+            Direction::East => (1, 0),
+            Direction::South => (0, 1),
+            Direction::West => (-1, 0),
+        }
+    }
+}
+
+pub struct Cell {
+    pub(crate) x: usize,
+    pub(crate) y: usize,
+    pub(crate) visited: bool,
+    pub(crate) slots: u8,
+    pub(crate) walls: [bool; MAX_SLOTS],
+}
+
+impl Cell {
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    pub fn y(&self) -> usize {
+        self.y
+    }
+
+    /// Walls in clockwise order. On square grids they are indexed by
+    /// [`Direction::index`]: north, east, south, west. Cells of a maze with
+    /// several levels also have a ceiling and a floor, walls 4 and 5, which
+    /// are open where stairs lead to the level above or below.
+    pub fn walls(&self) -> &[bool] {
+        &self.walls[..usize::from(self.slots)]
+    }
+
+    pub fn has_wall(&self, direction: Direction) -> bool {
+        self.walls[direction.index()]
+    }
+
+    /// Indices of the cell's wall slots.
+    pub(crate) fn slots(&self) -> Range<usize> {
+        0..usize::from(self.slots)
+    }
+
+    /// The walls of a square cell.
+    pub(crate) fn square_walls(&self) -> [bool; 4] {
+        [self.walls[0], self.walls[1], self.walls[2], self.walls[3]]
+    }
+}
+
+pub struct Maze {
+    pub(crate) topology: Topology,
+    pub(crate) wrap: Wrap,
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    /// Number of levels, stacked as consecutive blocks of `height` rows.
+    pub(crate) depth: usize,
+    pub(crate) cells: Vec<Cell>,
+    /// Index of the first cell of every ring of a polar maze, followed by the
+    /// number of cells. Empty for the other topologies.
+    pub(crate) ring_starts: Vec<usize>,
+    pub(crate) entrance: Option<(usize, usize)>,
+    pub(crate) exit: Option<(usize, usize)>,
+}
+
+impl Maze {
+    /// Creates a square maze in which every cell is closed on all four sides.
+    pub fn new(width: usize, height: usize) -> Self {
+        Maze::with_topology(Topology::Square, width, height)
+    }
+
+    /// Creates a maze of `width` by `height` cells of the given shape, with
+    /// every wall in place.
+    ///
+    /// A [`Topology::Polar`] maze has `height` rings and ignores `width`. The
+    /// center is a single cell and the first ring around it has six; every
+    /// further ring splits each cell of the ring inside it in two when that
+    /// keeps the cells closer to square. The width of a polar maze is the
+    /// number of cells in its outer ring.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cells would not hold together, see
+    /// [`Topology::check_size`].
+    pub fn with_topology(topology: Topology, width: usize, height: usize) -> Self {
+        if let Err(message) = topology.check_size(width, height) {
+            panic!("{}", message);
+        }
+        Maze::build(topology, width, height, 1)
+    }
+
+    /// Creates a square maze of `depth` levels of `width` by `height` cells
+    /// each, with every wall in place, including the ceilings and floors
+    /// between the levels.
+    ///
+    /// The levels are stacked top to bottom as blocks of rows: cell `(x, y)`
+    /// lies in row `y % height` of level `y / height`, so level 0 holds rows
+    /// `0..height`, level 1 rows `height..2 * height` and so on. Stairs lead
+    /// up to the level with the next lower number and down to the next
+    /// higher one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `depth` is 0.
+    pub fn with_levels(width: usize, height: usize, depth: usize) -> Self {
+        assert!(depth >= 1, "a maze needs at least one level");
+        Maze::build(Topology::Square, width, height, depth)
+    }
+
+    fn build(topology: Topology, width: usize, height: usize, depth: usize) -> Self {
+        let rows = match topology {
+            Topology::Polar => polar_rings(height),
+            _ => vec![width; height * depth],
+        };
+        let cells = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, &len)| {
+                let slots = match topology {
+                    Topology::Polar => polar_slots(&rows, y),
+                    _ if depth > 1 => DOWN + 1,
+                    _ => topology.slots(),
+                } as u8;
+                (0..len).map(move |x| Cell {
+                    x,
+                    y,
+                    visited: false,
+                    slots,
+                    walls: [true; MAX_SLOTS],
+                })
+            })
+            .collect();
+        let ring_starts = match topology {
+            Topology::Polar => std::iter::once(0)
+                .chain(rows.iter().scan(0, |start, &len| {
+                    *start += len;
+                    Some(*start)
+                }))
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Maze {
+            topology,
+            wrap: Wrap::None,
+            width: rows.last().copied().unwrap_or(width),
+            height,
+            depth,
+            cells,
+            ring_starts,
+            entrance: None,
+            exit: None,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows of a level, or of rings of a polar maze.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Number of levels, see [`Maze::with_levels`].
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Number of rows of all levels together.
+    pub(crate) fn rows(&self) -> usize {
+        self.height * self.depth
+    }
+
+    /// Row-major index of the cell at `(x, y)` in [`Maze::cells`]. In a polar
+    /// maze `x` counts the cells of ring `y` clockwise from north.
+    pub fn get_index(&self, x: usize, y: usize) -> usize {
+        match self.topology {
+            Topology::Polar => self.ring_starts[y] + x,
+            _ => y * self.width + x,
+        }
+    }
+
+    /// Number of cells in row `y`, or in ring `y` of a polar maze.
+    pub fn row_len(&self, y: usize) -> usize {
+        match self.topology {
+            Topology::Polar => self.ring_starts[y + 1] - self.ring_starts[y],
+            _ => self.width,
+        }
+    }
+
+    /// Whether `(x, y)` is a cell of the maze.
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        y < self.rows() && x < self.row_len(y)
+    }
+
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    pub fn cell(&self, x: usize, y: usize) -> &Cell {
+        &self.cells[self.get_index(x, y)]
+    }
+
+    /// Whether the square cell `(x, y)` has a wall on side `direction`.
+    pub fn has_wall(&self, x: usize, y: usize, direction: Direction) -> bool {
+        self.cell(x, y).has_wall(direction)
+    }
+
+    /// The cell next to the square cell `(x, y)` in `direction` on the same
+    /// level, or `None` at the edge of the grid. Edges joined by
+    /// [`Maze::wrap`] lead to the cell on the opposite edge.
+    pub fn neighbor(&self, x: usize, y: usize, direction: Direction) -> Option<(usize, usize)> {
+        let (level, y) = (y / self.height.max(1), y % self.height.max(1));
+        let (nx, ny) = self.level_neighbor(x, y, direction)?;
+        Some((nx, level * self.height + ny))
+    }
+
+    /// [`Maze::neighbor`] within a single level.
+    fn level_neighbor(&self, x: usize, y: usize, direction: Direction) -> Option<(usize, usize)> {
+        let (dx, dy) = direction.offset();
+        let (w, h) = (self.width as isize, self.height as isize);
+        let (nx, ny) = (x as isize + dx, y as isize + dy);
+        if (0..h).contains(&ny) && (0..w).contains(&nx) {
+            Some((nx as usize, ny as usize))
+        } else if !(0..h).contains(&ny) && self.wrap.wraps_y() {
+            Some((nx as usize, ny.rem_euclid(h) as usize))
+        } else if !(0..w).contains(&nx) && self.wrap == Wrap::Mobius {
+            Some((nx.rem_euclid(w) as usize, (h - 1 - ny) as usize))
+        } else if !(0..w).contains(&nx) && self.wrap.wraps_x() {
+            Some((nx.rem_euclid(w) as usize, ny as usize))
+        } else {
+            None
+        }
+    }
+
+    /// All cells adjacent to `(x, y)`, whether or not a wall separates them,
+    /// in the order of the walls between them.
+    pub fn neighbors(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let idx = self.get_index(x, y);
+        self.cells[idx]
+            .slots()
+            .filter_map(move |slot| self.neighbor_at(idx, slot))
+            .map(|n_idx| self.coords(n_idx))
+    }
+
+    /// Cells that can be reached from `(x, y)` in one step.
+    pub fn passages(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.open_neighbors(self.get_index(x, y))
+            .map(|n_idx| self.coords(n_idx))
+    }
+
+    /// Indices of the cells reachable in one step from the cell at `idx`.
+    pub(crate) fn open_neighbors(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        let cell = &self.cells[idx];
+        cell.slots()
+            .filter(move |&slot| !cell.walls[slot])
+            .filter_map(move |slot| self.neighbor_at(idx, slot))
+    }
+
+    /// Indices of all cells adjacent to the cell at `idx`, in slot order,
+    /// whether or not a wall separates them.
+    pub(crate) fn adjacent(&self, idx: usize) -> impl Iterator<Item = usize> + '_ {
+        self.cells[idx]
+            .slots()
+            .filter_map(move |slot| self.neighbor_at(idx, slot))
+    }
+
+    /// Removes the wall between the adjacent cells at `idx` and `other`.
+    pub(crate) fn link(&mut self, idx: usize, other: usize) {
+        let slot = self
+            .slot_towards(idx, other)
+            .expect("walls can only be removed between adjacent cells");
+        self.set_slot(idx, slot, false);
+    }
+
+    /// A cell picked uniformly at random. On rectangular grids the column
+    /// is drawn before the row.
+    pub(crate) fn random_cell<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        match self.topology {
+            Topology::Polar => rng.gen_range(0..self.cells.len()),
+            _ => {
+                let x = rng.gen_range(0..self.width);
+                let y = rng.gen_range(0..self.rows());
+                self.get_index(x, y)
+            }
+        }
+    }
+
+    /// Coordinates of the cell at `idx` in [`Maze::cells`].
+    pub(crate) fn coords(&self, idx: usize) -> (usize, usize) {
+        (self.cells[idx].x, self.cells[idx].y)
+    }
+
+    /// Removes the wall between two adjacent cells.
+    pub fn remove_wall(&mut self, x1: usize, y1: usize, x2: usize, y2: usize) {
+        self.set_wall(x1, y1, x2, y2, false);
+    }
+
+    /// Puts back the wall between two adjacent cells.
+    pub fn add_wall(&mut self, x1: usize, y1: usize, x2: usize, y2: usize) {
+        self.set_wall(x1, y1, x2, y2, true);
+    }
+
+    fn set_wall(&mut self, x1: usize, y1: usize, x2: usize, y2: usize, wall: bool) {
+        let idx1 = self.get_index(x1, y1);
+        let idx2 = self.get_index(x2, y2);
+        let slot = self
+            .slot_towards(idx1, idx2)
+            .expect("walls can only be changed between adjacent cells");
+        self.set_slot(idx1, slot, wall);
+    }
+
+    /// Sets wall `slot` of the cell at `idx` and the same wall seen from the
+    /// neighbor behind it.
+    pub(crate) fn set_slot(&mut self, idx: usize, slot: usize, wall: bool) {
+        self.cells[idx].walls[slot] = wall;
+        if let Some(n_idx) = self.neighbor_at(idx, slot) {
+            let back = self.back_slot(idx, slot);
+            self.cells[n_idx].walls[back] = wall;
+        }
+    }
+
+    /// Breadth-first search through open passages from `start`, writing the
+    /// distance of every reached cell into `dist`. Cells already holding a
+    /// distance are treated as visited.
+    ///
+    /// Returns the reached cells in order of increasing distance.
+    pub(crate) fn bfs(&self, start: usize, dist: &mut [usize]) -> Vec<usize> {
+        let mut order = vec![start];
+        dist[start] = 0;
+
+        let mut head = 0;
+        while head < order.len() {
+            let idx = order[head];
+            head += 1;
+
+            for n_idx in self.open_neighbors(idx) {
+                if dist[n_idx] == UNREACHED {
+                    dist[n_idx] = dist[idx] + 1;
+                    order.push(n_idx);
+                }
+            }
+        }
+
+        order
+    }
+
+    pub fn entrance(&self) -> Option<(usize, usize)> {
+        self.entrance
+    }
+
+    pub fn exit(&self) -> Option<(usize, usize)> {
+        self.exit
+    }
+
+    /// Opens the outer wall of the boundary cell `(x, y)` and marks it as the entrance.
+    pub fn set_entrance(&mut self, x: usize, y: usize) -> Result<(), String> {
+        self.open_boundary(x, y)?;
+        self.entrance = Some((x, y));
+        Ok(())
+    }
+
+    /// Opens the outer wall of the boundary cell `(x, y)` and marks it as the exit.
+    pub fn set_exit(&mut self, x: usize, y: usize) -> Result<(), String> {
+        self.open_boundary(x, y)?;
+        self.exit = Some((x, y));
+        Ok(())
+    }
+
+    /// The side of the square cell `(x, y)` that faces out of the maze,
+    /// preferring the top and bottom edges for corner cells, or `None` for
+    /// interior cells. Edges joined by [`Maze::wrap`] do not face out, and
+    /// every level of a maze with several levels has its own edges.
+    pub fn boundary_side(&self, x: usize, y: usize) -> Option<Direction> {
+        if x >= self.width || y >= self.rows() {
+            return None;
+        }
+        let (rows, columns) = (!self.wrap.wraps_y(), !self.wrap.wraps_x());
+        let row = y % self.height;
+        if rows && row == 0 {
+            Some(Direction::North)
+        } else if rows && row == self.height - 1 {
+            Some(Direction::South)
+        } else if columns && x == 0 {
+            Some(Direction::West)
+        } else if columns && x == self.width - 1 {
+            Some(Direction::East)
+        } else {
+            None
+        }
+    }
+
+    /// Every cell on the outer edge of the maze, in the order of [`Maze::cells`].
+    pub fn boundary_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.cells.len())
+            .filter(move |&idx| self.boundary_slot(idx).is_some())
+            .map(move |idx| self.coords(idx))
+    }
+
+    /// The boundary cell with the longest path from `from`, `from` itself when
+    /// no boundary cell can be reached, or `None` when `from` is not a cell of
+    /// the maze.
+    pub fn farthest_boundary_cell(&self, from: (usize, usize)) -> Option<(usize, usize)> {
+        if !self.contains(from.0, from.1) {
+            return None;
+        }
+        let mut dist = vec![UNREACHED; self.cells.len()];
+        self.bfs(self.get_index(from.0, from.1), &mut dist);
+        let farthest = self
+            .boundary_cells()
+            .filter(|&(x, y)| dist[self.get_index(x, y)] != UNREACHED)
+            .max_by_key(|&(x, y)| dist[self.get_index(x, y)]);
+        Some(farthest.unwrap_or(from))
+    }
+
+    /// Two boundary cells as far apart as possible: the ends of the longest
+    /// path in the maze that starts and finishes on the outer edge. `None`
+    /// for a maze without cells.
+    pub fn farthest_boundary_pair(&self) -> Option<((usize, usize), (usize, usize))> {
+        let first = self.farthest_boundary_cell((0, 0))?;
+        Some((first, self.farthest_boundary_cell(first)?))
+    }
+
+    fn open_boundary(&mut self, x: usize, y: usize) -> Result<(), String> {
+        let not_on_edge = || {
+            format!(
+                "({}, {}) is not a cell on the edge of the {}x{} maze",
+                x, y, self.width, self.height
+            )
+        };
+        if !self.contains(x, y) {
+            return Err(not_on_edge());
+        }
+        let idx = self.get_index(x, y);
+        let slot = self.boundary_slot(idx).ok_or_else(not_on_edge)?;
+        self.cells[idx].walls[slot] = false;
+        Ok(())
+    }
+
+    pub fn print(&self) {
+        self.write_ascii(&mut io::stdout().lock(), None)
+            .expect("failed printing to stdout");
+    }
+
+    /// Writes the maze in the `+---+` format. The cells of `path` and the
+    /// openings between them are marked with `*`, the entrance with `S` and
+    /// the exit with `E`. Passages through the seams of a wrapped maze are
+    /// dotted, `...` on the top and bottom edges and `:` on the sides.
+    ///
+    /// The levels of a maze with several levels are drawn one below the
+    /// other, separated by an empty line, with `<` in cells that have stairs
+    /// up and `>` in cells that have stairs down.
+    ///
+    /// Hex and delta mazes are drawn with `/`, `\` and `_` instead and polar
+    /// mazes are unrolled into rows, marking only the cells.
+    pub fn write_ascii<W: Write>(
+        &self,
+        out: &mut W,
+        path: Option<&[(usize, usize)]>,
+    ) -> io::Result<()> {
+        match self.topology {
+            Topology::Square => {}
+            Topology::Hex => return self.write_hex_ascii(out, path),
+            Topology::Polar => return self.write_polar_ascii(out, path),
+            Topology::Delta => return self.write_delta_ascii(out, path),
+        }
+        let overlays = self.overlays(path);
+        for level in 0..self.depth {
+            if level > 0 {
+                out.write_all(b"\n")?;
+            }
+            let mut walls = Vec::new();
+            let mut last_row = 0..0;
+            for y in level * self.height..(level + 1) * self.height {
+                let row = y * self.width..(y + 1) * self.width;
+                walls = self.cells[row.clone()]
+                    .iter()
+                    .map(Cell::square_walls)
+                    .collect();
+                write_ascii_row(out, &walls, &overlays[row.clone()])?;
+                last_row = row;
+            }
+            write_ascii_bottom(out, self.width, &walls, &overlays[last_row])?;
+        }
+        Ok(())
+    }
+
+    /// The label, path links, seams and stairs of every cell for `path` and
+    /// the openings.
+    pub(crate) fn overlays(&self, path: Option<&[(usize, usize)]>) -> Vec<Overlay> {
+        let mut overlays = vec![Overlay::BLANK; self.cells.len()];
+        if self.depth > 1 {
+            for (cell, overlay) in self.cells.iter().zip(&mut overlays) {
+                overlay.stairs = [!cell.walls[UP], !cell.walls[DOWN]];
+            }
+        }
+        if !self.wrap.is_none() {
+            for (idx, overlay) in overlays.iter_mut().enumerate() {
+                for direction in Direction::ALL {
+                    overlay.seams[direction.index()] = self.seam(idx, direction.index());
+                }
+            }
+        }
+        for &(x, y) in path.unwrap_or_default() {
+            overlays[self.get_index(x, y)].label = b'*';
+        }
+        for step in path.unwrap_or_default().windows(2) {
+            let (from, to) = (step[0], step[1]);
+            if let Some(direction) = self.step_direction(from, to) {
+                overlays[self.get_index(from.0, from.1)].links[direction.index()] = true;
+                overlays[self.get_index(to.0, to.1)].links[direction.opposite().index()] = true;
+            }
+        }
+        if let Some((x, y)) = self.entrance {
+            overlays[self.get_index(x, y)].label = b'S';
+        }
+        if let Some((x, y)) = self.exit {
+            overlays[self.get_index(x, y)].label = b'E';
+        }
+        overlays
+    }
+
+    /// Whether the horizontal grid line `line`, counted from the top, has a
+    /// wall above or below column `x`. Every level has `height + 1` lines of
+    /// its own, counted on from those of the levels above it.
+    pub(crate) fn horizontal_wall(&self, x: usize, line: usize) -> bool {
+        let (level, line) = (line / (self.height + 1), line % (self.height + 1));
+        let y = level * self.height + line;
+        if line < self.height {
+            self.has_wall(x, y, Direction::North)
+        } else {
+            line > 0 && self.has_wall(x, y - 1, Direction::South)
+        }
+    }
+
+    /// Whether the vertical grid line `line`, counted from the left, has a
+    /// wall next to row `y`.
+    pub(crate) fn vertical_wall(&self, line: usize, y: usize) -> bool {
+        if line < self.width {
+            self.has_wall(line, y, Direction::West)
+        } else {
+            line > 0 && self.has_wall(line - 1, y, Direction::East)
+        }
+    }
+
+    /// Whether the horizontal grid line `line`, counted as in
+    /// [`Maze::horizontal_wall`], is a seam joining the top and bottom edges.
+    pub(crate) fn horizontal_seam(&self, line: usize) -> bool {
+        let line = line % (self.height + 1);
+        self.wrap.wraps_y() && (line == 0 || line == self.height)
+    }
+
+    /// Whether the vertical grid line `line` is a seam joining the left and
+    /// right edges.
+    pub(crate) fn vertical_seam(&self, line: usize) -> bool {
+        self.wrap.wraps_x() && (line == 0 || line == self.width)
+    }
+}
+
+/// What the ASCII renderer draws inside a cell, which of its openings the
+/// solution path runs through, which of its sides are seams and whether it
+/// has stairs up and down.
+#[derive(Clone, Copy)]
+pub(crate) struct Overlay {
+    pub(crate) label: u8,
+    pub(crate) links: [bool; 4],
+    pub(crate) seams: [bool; 4],
+    pub(crate) stairs: [bool; 2],
+}
+
+impl Overlay {
+    const BLANK: Overlay = Overlay {
+        label: b' ',
+        links: [false; 4],
+        seams: [false; 4],
+        stairs: [false; 2],
+    };
+}
+
+/// Writes rows of cell walls in the `+---+` format used by [`Maze::print`].
+///
+/// Rows are written as they are produced, which lets generators such as
+/// [`EllerRows`](crate::EllerRows) stream mazes that never exist in memory as a whole.
+pub fn write_ascii_rows<W, I>(out: &mut W, width: usize, rows: I) -> io::Result<()>
+where
+    W: Write,
+    I: IntoIterator<Item = Vec<[bool; 4]>>,
+{
+    let overlays = vec![Overlay::BLANK; width];
+    let mut last_row = Vec::new();
+    for row in rows {
+        write_ascii_row(out, &row, &overlays)?;
+        last_row = row;
+    }
+    write_ascii_bottom(out, width, &last_row, &overlays)
+}
+
+fn write_ascii_row<W: Write>(
+    out: &mut W,
+    walls: &[[bool; 4]],
+    overlays: &[Overlay],
+) -> io::Result<()> {
+    out.write_all(b"+")?;
+    for (walls, overlay) in walls.iter().zip(overlays) {
+        out.write_all(if walls[0] {
+            b"---+"
+        } else if overlay.links[0] {
+            b" * +"
+        } else if overlay.seams[0] {
+            b"...+"
+        } else {
+            b"   +"
+        })?;
+    }
+    out.write_all(b"\n")?;
+
+    for (walls, overlay) in walls.iter().zip(overlays) {
+        out.write_all(if walls[3] {
+            b"|"
+        } else if overlay.links[3] {
+            b"*"
+        } else if overlay.seams[3] {
+            b":"
+        } else {
+            b" "
+        })?;
+        let up = if overlay.stairs[0] { b'<' } else { b' ' };
+        let down = if overlay.stairs[1] { b'>' } else { b' ' };
+        out.write_all(&[up, overlay.label, down])?;
+    }
+    let east = walls.iter().zip(overlays).next_back();
+    out.write_all(match east {
+        Some((walls, _)) if walls[1] => b"|\n",
+        Some((_, overlay)) if overlay.links[1] => b"*\n",
+        Some((_, overlay)) if overlay.seams[1] => b":\n",
+        _ => b" \n",
+    })
+}
+
+/// Writes the bottom border below `walls`, the last row of the maze. Missing
+/// walls are treated as closed so an empty maze still gets a border.
+fn write_ascii_bottom<W: Write>(
+    out: &mut W,
+    width: usize,
+    walls: &[[bool; 4]],
+    overlays: &[Overlay],
+) -> io::Result<()> {
+    for x in 0..width {
+        let open = walls.get(x).is_some_and(|walls| !walls[2]);
+        let overlay = overlays.get(x).unwrap_or(&Overlay::BLANK);
+        out.write_all(match (open, overlay.links[2], overlay.seams[2]) {
+            (false, _, _) => b"+---",
+            (true, true, _) => b"+ * ",
+            (true, false, true) => b"+...",
+            (true, false, false) => b"+   ",
+        })?;
+    }
+    out.write_all(b"+\n")
+}
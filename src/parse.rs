@@ -0,0 +1,276 @@
+//! Reading mazes back from text drawings.
+
+use crate::maze::{Direction, Maze};
+use crate::render::text::{JUNCTIONS, QUADRANTS};
+
+/// Characters that stand for a wall in block drawings.
+const BLOCKS: [char; 5] = ['#', '█', '▓', '▒', 'X'];
+
+/// Characters that may appear in the passages of a drawing: the solution
+/// path and the entrance and exit markers.
+const MARKS: [char; 5] = ['*', '.', 'S', 'E', ' '];
+
+/// Characters the text styles draw open passages through the seams of a
+/// wrapped maze with, besides `...` in the `+---+` border.
+const SEAMS: [char; 3] = [':', '┆', '┄'];
+
+impl Maze {
+    /// Parses a maze drawn as text. These kinds of drawings are understood:
+    ///
+    /// * the `+---+` format written by [`Maze::print`], where `-` and `|`
+    ///   are walls and the cells may be any number of characters wide,
+    /// * the box-drawing and quadrant block styles of [`Maze::write_text`],
+    /// * block drawings, where walls are `#`, `█`, `▓`, `▒` or `X` and every
+    ///   cell, wall and corner takes one character, so a maze of `w` by `h`
+    ///   cells is `2w + 1` characters wide and `2h + 1` lines tall.
+    ///
+    /// Lines before and after the drawing, such as the report printed with
+    /// it, are skipped as long as they are empty or start with a letter or
+    /// digit. Cells marked `S` and `E` become the entrance and the exit, `*`
+    /// and `.` path markers are ignored.
+    ///
+    /// Only square mazes of a single level without wrapped edges can be read
+    /// back; the drawings of the others fail to parse.
+    pub fn from_ascii(text: &str) -> Result<Maze, String> {
+        let all: Vec<&str> = text.lines().collect();
+        let start = all
+            .iter()
+            .position(|line| is_drawing(line))
+            .ok_or_else(|| "no maze drawing found".to_string())?;
+        let end = all[start..]
+            .iter()
+            .position(|line| !is_drawing(line) && !is_passage(line))
+            .map_or(all.len(), |len| start + len);
+        let lines: Vec<Vec<char>> = all[start..end]
+            .iter()
+            .map(|line| line.trim_end().chars().collect())
+            .collect();
+
+        // A line right before or after the drawing that is not part of the
+        // report belongs to a maze these rules cannot read, so refuse it
+        // rather than load only the part above or below.
+        let outside: Vec<(usize, &str)> = [start.checked_sub(1), Some(end)]
+            .into_iter()
+            .flatten()
+            .filter_map(|n| all.get(n).map(|line| (n, *line)))
+            .filter(|(_, line)| !is_report(line))
+            .collect();
+        let seam = |line: &str| line.contains(SEAMS);
+        let border = |line: &[char]| line.first() == Some(&'+') && line.contains(&'.');
+        if outside.iter().any(|(_, line)| seam(line))
+            || all[start..end].iter().any(|line| seam(line))
+            || border(&lines[0])
+            || border(&lines[lines.len() - 1])
+        {
+            return Err(
+                "drawings of wrapped mazes cannot be loaded, save the maze as JSON instead"
+                    .to_string(),
+            );
+        }
+        if let Some((n, line)) = outside.first() {
+            return Err(format!(
+                "the maze drawing is cut off at line {}: '{}'",
+                n + 1,
+                line.trim_end()
+            ));
+        }
+
+        let any = |set: &[char]| lines.iter().flatten().any(|c| set.contains(c));
+        let mut maze = if lines[0].contains(&'+') {
+            parse_plus(&lines)?
+        } else if any(&JUNCTIONS[1..]) {
+            parse_plus(&from_box_drawing(&lines))?
+        } else if any(&QUADRANTS[1..15]) {
+            parse_blocks(&from_quadrants(&lines))?
+        } else {
+            parse_blocks(&lines)?
+        };
+        let entrance = maze.entrance.take();
+        let exit = maze.exit.take();
+        for (name, opening) in [("entrance", entrance), ("exit", exit)] {
+            if let Some((x, y)) = opening {
+                if maze.boundary_side(x, y).is_none() {
+                    return Err(format!(
+                        "{} ({}, {}) is not a cell on the edge of the maze",
+                        name, x, y
+                    ));
+                }
+            }
+        }
+        maze.entrance = entrance;
+        maze.exit = exit;
+        Ok(maze)
+    }
+}
+
+fn is_drawing(line: &str) -> bool {
+    let line = line.trim_end();
+    let only = |set: &[char]| line.chars().all(|c| set.contains(&c) || MARKS.contains(&c));
+    (only(&['+', '-', '|']) && line.contains(['+', '|']))
+        || (only(&BLOCKS) && line.contains(BLOCKS))
+        || (only(&JUNCTIONS) && line.contains(&JUNCTIONS[1..]))
+        || (only(&QUADRANTS) && line.contains(&QUADRANTS[1..]))
+}
+
+/// A line of text around a drawing, such as the report printed with it.
+fn is_report(line: &str) -> bool {
+    line.trim().is_empty() || line.starts_with(char::is_alphanumeric)
+}
+
+/// A row of a drawing that has no walls at all, only markers.
+fn is_passage(line: &str) -> bool {
+    !line.trim().is_empty() && line.chars().all(|c| MARKS.contains(&c))
+}
+
+/// The character at `column`, treating the end of a trimmed line as spaces.
+fn at(line: &[char], column: usize) -> char {
+    line.get(column).copied().unwrap_or(' ')
+}
+
+fn parse_plus(lines: &[Vec<char>]) -> Result<Maze, String> {
+    if lines.len() < 3 || lines.len().is_multiple_of(2) {
+        return Err(format!(
+            "a +---+ drawing needs an odd number of lines, found {}",
+            lines.len()
+        ));
+    }
+    // The corners in the top border give the columns of the vertical walls.
+    let corners = |line: &[char]| -> Vec<usize> {
+        (0..line.len())
+            .filter(|&column| line[column] == '+')
+            .collect()
+    };
+    let columns = corners(&lines[0]);
+    if columns.len() < 2 {
+        return Err("the top border needs at least two '+' corners".to_string());
+    }
+    if let Some(row) = (2..lines.len())
+        .step_by(2)
+        .find(|&row| corners(&lines[row]) != columns)
+    {
+        return Err(format!(
+            "the corners in line {} of the drawing do not line up with the top border",
+            row + 1
+        ));
+    }
+
+    let mut maze = Maze::new(columns.len() - 1, lines.len() / 2);
+    let horizontal = |line: &[char], x: usize| {
+        (columns[x] + 1..columns[x + 1]).any(|column| at(line, column) == '-')
+    };
+    for y in 0..maze.height {
+        let (above, middle, below) = (&lines[2 * y], &lines[2 * y + 1], &lines[2 * y + 2]);
+        for x in 0..maze.width {
+            let idx = maze.get_index(x, y);
+            let walls = &mut maze.cells[idx].walls;
+            walls[Direction::North.index()] = horizontal(above, x);
+            walls[Direction::South.index()] = horizontal(below, x);
+            walls[Direction::West.index()] = at(middle, columns[x]) == '|';
+            walls[Direction::East.index()] = at(middle, columns[x + 1]) == '|';
+
+            let inside: Vec<char> = (columns[x] + 1..columns[x + 1])
+                .map(|column| at(middle, column))
+                .collect();
+            mark(&mut maze, &inside, (x, y));
+        }
+    }
+    Ok(maze)
+}
+
+/// Translates the box-drawing style, four characters per cell, into the
+/// `+---+` format.
+fn from_box_drawing(lines: &[Vec<char>]) -> Vec<Vec<char>> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(row, line)| {
+            line.iter()
+                .enumerate()
+                .map(|(column, &c)| match c {
+                    _ if row % 2 == 0 && column % 4 == 0 => '+',
+                    '─' => '-',
+                    '│' => '|',
+                    c => c,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Splits every quadrant character into two by two blocks. The last block
+/// row and column lie outside the maze and are dropped.
+fn from_quadrants(lines: &[Vec<char>]) -> Vec<Vec<char>> {
+    let mut blocks = Vec::new();
+    for line in lines {
+        let bits: Vec<usize> = line
+            .iter()
+            .map(|c| QUADRANTS.iter().position(|q| q == c).unwrap_or(0))
+            .collect();
+        for half in [0, 2] {
+            let row: Vec<char> = bits
+                .iter()
+                .flat_map(|&bits| [bits >> half & 1, bits >> half >> 1 & 1])
+                .map(|filled| if filled == 1 { '#' } else { ' ' })
+                .collect();
+            blocks.push(row);
+        }
+    }
+    blocks.pop();
+    for row in &mut blocks {
+        row.pop();
+    }
+    blocks
+}
+
+fn parse_blocks(lines: &[Vec<char>]) -> Result<Maze, String> {
+    let columns = lines.iter().map(Vec::len).max().unwrap_or(0);
+    let (columns, rows) = (columns + 1 - columns % 2, lines.len());
+    if rows < 3 || rows.is_multiple_of(2) {
+        return Err(format!(
+            "a block drawing needs an odd number of lines, found {}",
+            rows
+        ));
+    }
+
+    let mut maze = Maze::new(columns / 2, rows / 2);
+    let wall = |column: usize, row: usize| BLOCKS.contains(&at(&lines[row], column));
+    for y in 0..maze.height {
+        for x in 0..maze.width {
+            let (column, row) = (2 * x + 1, 2 * y + 1);
+            let idx = maze.get_index(x, y);
+            let walls = &mut maze.cells[idx].walls;
+            walls[Direction::North.index()] = wall(column, row - 1);
+            walls[Direction::South.index()] = wall(column, row + 1);
+            walls[Direction::West.index()] = wall(column - 1, row);
+            walls[Direction::East.index()] = wall(column + 1, row);
+
+            // Block drawings often mark the openings in the outer wall
+            // rather than the cells behind them.
+            let mut marks = vec![at(&lines[row], column)];
+            if y == 0 {
+                marks.push(at(&lines[row - 1], column));
+            }
+            if y == maze.height - 1 {
+                marks.push(at(&lines[row + 1], column));
+            }
+            if x == 0 {
+                marks.push(at(&lines[row], column - 1));
+            }
+            if x == maze.width - 1 {
+                marks.push(at(&lines[row], column + 1));
+            }
+            mark(&mut maze, &marks, (x, y));
+        }
+    }
+    Ok(maze)
+}
+
+/// Records `cell` as the entrance or exit if `text` holds an `S` or `E`.
+fn mark(maze: &mut Maze, text: &[char], cell: (usize, usize)) {
+    if text.contains(&'S') {
+        maze.entrance = Some(cell);
+    }
+    if text.contains(&'E') {
+        maze.exit = Some(cell);
+    }
+}
@@ -0,0 +1,115 @@
+use crate::maze::{Maze, UNREACHED};
+
+pub struct MazeQuality {
+    pub dead_ends: usize,
+    pub longest_path: usize,
+    pub avg_path_length: f64,
+    pub branching_factor: f64,
+}
+
+impl Maze {
+    pub fn measure_quality(&self) -> MazeQuality {
+        let dead_ends = self.count_dead_ends();
+        let (longest_path, total_path_length, total_paths) = self.measure_paths();
+        let branching_factor = self.calculate_branching_factor();
+
+        MazeQuality {
+            dead_ends,
+            longest_path,
+            avg_path_length: ratio(total_path_length, total_paths),
+            branching_factor,
+        }
+    }
+
+    fn count_dead_ends(&self) -> usize {
+        self.cells
+            .iter()
+            .filter(|&cell| cell.walls().iter().filter(|&&wall| !wall).count() == 1)
+            .count()
+    }
+
+    /// Longest path, summed eccentricities and summed path counts over all cells.
+    ///
+    /// Each connected region is handled with three breadth-first sweeps: the
+    /// first finds one end `a` of the longest path, the second its other end `b`,
+    /// and the distances from `a` and `b` give every cell's eccentricity. The
+    /// number of maximal paths leaving a cell is the number of dead ends other
+    /// than the cell itself. Both are exact for perfect mazes and are computed
+    /// on shortest paths when the maze contains loops.
+    fn measure_paths(&self) -> (usize, usize, usize) {
+        let total = self.cells.len();
+        let mut from_a = vec![UNREACHED; total];
+        let mut from_b = vec![UNREACHED; total];
+
+        let mut longest_path = 0;
+        let mut total_path_length = 0;
+        let mut total_paths = 0;
+
+        for start in 0..total {
+            if from_a[start] != UNREACHED {
+                continue;
+            }
+
+            let a = *self.bfs(start, &mut from_b).last().unwrap();
+            let region = self.bfs(a, &mut from_a);
+            let b = *region.last().unwrap();
+            for &idx in &region {
+                from_b[idx] = UNREACHED;
+            }
+            self.bfs(b, &mut from_b);
+
+            let degrees: Vec<usize> = region.iter().map(|&idx| self.degree(idx)).collect();
+            let leaves = degrees.iter().filter(|&&degree| degree == 1).count();
+
+            longest_path = longest_path.max(from_a[b]);
+            for (&idx, &degree) in region.iter().zip(&degrees) {
+                total_path_length += from_a[idx].max(from_b[idx]);
+                total_paths += (leaves - usize::from(degree == 1)).max(1);
+            }
+        }
+
+        (longest_path, total_path_length, total_paths)
+    }
+
+    fn degree(&self, idx: usize) -> usize {
+        let cell = &self.cells[idx];
+        self.passages(cell.x, cell.y).count()
+    }
+
+    fn calculate_branching_factor(&self) -> f64 {
+        let total_branches: usize = self
+            .cells
+            .iter()
+            .map(|cell| cell.walls().iter().filter(|&&wall| !wall).count())
+            .sum();
+
+        ratio(total_branches, self.cells.len())
+    }
+}
+
+/// `numerator / denominator`, or 0 for a maze without cells to average over.
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 {
+        return 0.0;
+    }
+    numerator as f64 / denominator as f64
+}
+
+pub fn calculate_quality_index(quality: &MazeQuality, maze_size: usize) -> f64 {
+    if maze_size == 0 {
+        return 0.0;
+    }
+    let dead_end_ratio = quality.dead_ends as f64 / maze_size as f64;
+    let path_length_ratio = quality.longest_path as f64 / maze_size as f64;
+    let normalized_avg_path = quality.avg_path_length / maze_size as f64;
+
+    let w_dead_ends = 0.25;
+    let w_longest_path = 0.3;
+    let w_avg_path = 0.25;
+    let w_branching = 0.2;
+
+    (1.0 - dead_end_ratio) * w_dead_ends
+        + path_length_ratio * w_longest_path
+        + normalized_avg_path * w_avg_path
+        + quality.branching_factor * w_branching
+}
@@ -0,0 +1,131 @@
+//! Drawings of [`Topology::Delta`](crate::Topology::Delta) mazes.
+
+use super::svg::SvgOptions;
+use crate::maze::{Direction, Maze};
+use crate::topology::{delta_sides, points_up};
+use std::io::{self, Write};
+
+impl Maze {
+    /// Writes a delta maze with `/`, `\` and `_`, three lines per row of
+    /// triangles:
+    ///
+    /// ```text
+    ///           ____
+    ///   /\ S         \
+    ///  /  \           \
+    /// /    \ ____      \
+    /// \                /
+    ///  \         E    /
+    ///   \ ____       /
+    /// ```
+    ///
+    /// Cells on `path` are marked with `*`, the openings with `S` and `E`.
+    pub(crate) fn write_delta_ascii<W: Write>(
+        &self,
+        out: &mut W,
+        path: Option<&[(usize, usize)]>,
+    ) -> io::Result<()> {
+        let mut canvas = vec![vec![b' '; 3 * self.width + 3]; 3 * self.height + 1];
+        let overlays = self.overlays(path);
+
+        for (idx, cell) in self.cells.iter().enumerate() {
+            let (top, left) = (3 * cell.y + 1, 3 * cell.x);
+            let up = points_up(cell.x, cell.y);
+            for (slot, side) in delta_sides(up).into_iter().enumerate() {
+                if !cell.walls[slot] {
+                    continue;
+                }
+                // Characters of the side as (line, column) from the top left
+                // of the cell.
+                let (c, chars): (u8, [(usize, usize); 3]) = match (up, side) {
+                    (true, Direction::West) => (b'/', [(0, 2), (1, 1), (2, 0)]),
+                    (true, Direction::East) => (b'\\', [(0, 3), (1, 4), (2, 5)]),
+                    (false, Direction::West) => (b'\\', [(0, 0), (1, 1), (2, 2)]),
+                    (false, Direction::East) => (b'/', [(0, 5), (1, 4), (2, 3)]),
+                    (_, Direction::South) => {
+                        canvas[top + 2][left + 1..left + 5].fill(b'_');
+                        continue;
+                    }
+                    (_, Direction::North) => {
+                        canvas[top - 1][left + 1..left + 5].fill(b'_');
+                        continue;
+                    }
+                };
+                for (line, column) in chars {
+                    canvas[top + line][left + column] = c;
+                }
+            }
+            let line = if up { top + 1 } else { top };
+            canvas[line][left + 2] = overlays[idx].label;
+        }
+
+        for line in canvas {
+            let line = String::from_utf8(line).expect("the drawing is ASCII");
+            writeln!(out, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+
+    /// Size of the SVG image of a delta maze. `cell_size` is the length of
+    /// the sides of a triangle.
+    pub(super) fn delta_svg_size(&self, options: &SvgOptions) -> (f64, f64) {
+        let size = options.cell_size;
+        (
+            (self.width + 1) as f64 * size / 2.0 + 2.0 * options.margin,
+            self.height as f64 * row_height(size) + 2.0 * options.margin,
+        )
+    }
+
+    /// Centroid of the triangle `(x, y)` in the SVG image.
+    pub(super) fn delta_svg_center(
+        &self,
+        (x, y): (usize, usize),
+        options: &SvgOptions,
+    ) -> (f64, f64) {
+        let size = options.cell_size;
+        let height = row_height(size);
+        let offset = if points_up(x, y) { 2.0 } else { 1.0 } * height / 3.0;
+        (
+            options.margin + (x + 1) as f64 * size / 2.0,
+            options.margin + y as f64 * height + offset,
+        )
+    }
+
+    /// Path data for every wall, one `M x y L x y` segment per side. A wall
+    /// between two cells is drawn by the cell with the lower index.
+    pub(super) fn delta_svg_walls(&self, options: &SvgOptions) -> String {
+        let size = options.cell_size;
+        let height = row_height(size);
+        let mut data = String::new();
+
+        for (idx, cell) in self.cells.iter().enumerate() {
+            let left = options.margin + cell.x as f64 * size / 2.0;
+            let (right, middle) = (left + size, left + size / 2.0);
+            let top = options.margin + cell.y as f64 * height;
+            let bottom = top + height;
+            let up = points_up(cell.x, cell.y);
+
+            for (slot, side) in delta_sides(up).into_iter().enumerate() {
+                let shared = self.neighbor_at(idx, slot).is_some_and(|n_idx| n_idx < idx);
+                if !cell.walls[slot] || shared {
+                    continue;
+                }
+                let ((x1, y1), (x2, y2)) = match (up, side) {
+                    (true, Direction::East) => ((middle, top), (right, bottom)),
+                    (true, Direction::West) => ((left, bottom), (middle, top)),
+                    (false, Direction::East) => ((right, top), (middle, bottom)),
+                    (false, Direction::West) => ((middle, bottom), (left, top)),
+                    (_, Direction::South) => ((right, bottom), (left, bottom)),
+                    (_, Direction::North) => ((left, top), (right, top)),
+                };
+                data.push_str(&format!("M{:.2} {:.2}L{:.2} {:.2}", x1, y1, x2, y2));
+            }
+        }
+        data
+    }
+}
+
+/// Height of a row of triangles with sides of length `size`.
+fn row_height(size: f64) -> f64 {
+    size * 3f64.sqrt() / 2.0
+}
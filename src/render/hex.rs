@@ -0,0 +1,107 @@
+//! Drawings of [`Topology::Hex`](crate::Topology::Hex) mazes. Cells are flat
+//! topped hexagons in columns, every odd column half a cell lower.
+
+use super::svg::SvgOptions;
+use crate::maze::Maze;
+use crate::topology::HexDirection;
+use std::io::{self, Write};
+
+impl Maze {
+    /// Writes a hex maze with `/`, `\` and `_`, two lines per row of cells:
+    ///
+    /// ```text
+    ///          ___
+    /// / S \___/   \___
+    /// \   /   \   /   \
+    /// /    ___     ___/
+    /// \___/    ___  E
+    ///     \___/   \___/
+    /// ```
+    ///
+    /// Cells on `path` are marked with `*`, the openings with `S` and `E`.
+    pub(crate) fn write_hex_ascii<W: Write>(
+        &self,
+        out: &mut W,
+        path: Option<&[(usize, usize)]>,
+    ) -> io::Result<()> {
+        let lines = 2 * self.height + if self.width > 1 { 2 } else { 1 };
+        let mut canvas = vec![vec![b' '; 4 * self.width + 1]; lines];
+        let overlays = self.overlays(path);
+
+        for (idx, cell) in self.cells.iter().enumerate() {
+            let top = 2 * cell.y + cell.x % 2;
+            let left = 4 * cell.x;
+            let mut draw = |direction: HexDirection, line: usize, columns: &[usize], c: u8| {
+                if cell.walls[direction.index()] {
+                    for &column in columns {
+                        canvas[top + line][left + column] = c;
+                    }
+                }
+            };
+            draw(HexDirection::North, 0, &[1, 2, 3], b'_');
+            draw(HexDirection::NorthEast, 1, &[4], b'\\');
+            draw(HexDirection::SouthEast, 2, &[4], b'/');
+            draw(HexDirection::South, 2, &[1, 2, 3], b'_');
+            draw(HexDirection::SouthWest, 2, &[0], b'\\');
+            draw(HexDirection::NorthWest, 1, &[0], b'/');
+            canvas[top + 1][left + 2] = overlays[idx].label;
+        }
+
+        for line in canvas {
+            let line = String::from_utf8(line).expect("the drawing is ASCII");
+            writeln!(out, "{}", line.trim_end())?;
+        }
+        Ok(())
+    }
+
+    /// Size of the SVG image of a hex maze. `cell_size` is the width of a
+    /// hexagon from corner to corner.
+    pub(super) fn hex_svg_size(&self, options: &SvgOptions) -> (f64, f64) {
+        let radius = options.cell_size / 2.0;
+        let columns = 1.5 * self.width as f64 + 0.5;
+        let rows = self.height as f64 + if self.width > 1 { 0.5 } else { 0.0 };
+        (
+            columns * radius + 2.0 * options.margin,
+            rows * 3f64.sqrt() * radius + 2.0 * options.margin,
+        )
+    }
+
+    /// Center of the hexagon of `(x, y)` in the SVG image.
+    pub(super) fn hex_svg_center(
+        &self,
+        (x, y): (usize, usize),
+        options: &SvgOptions,
+    ) -> (f64, f64) {
+        let radius = options.cell_size / 2.0;
+        let row = y as f64 + 0.5 + if x % 2 == 1 { 0.5 } else { 0.0 };
+        (
+            options.margin + radius * (1.0 + 1.5 * x as f64),
+            options.margin + row * 3f64.sqrt() * radius,
+        )
+    }
+
+    /// Path data for every wall, one `M x y L x y` segment per side. A wall
+    /// between two cells is drawn by the cell with the lower index.
+    pub(super) fn hex_svg_walls(&self, options: &SvgOptions) -> String {
+        let radius = options.cell_size / 2.0;
+        let mut data = String::new();
+
+        for (idx, cell) in self.cells.iter().enumerate() {
+            let (cx, cy) = self.hex_svg_center((cell.x, cell.y), options);
+            // Corners clockwise from the east one; side `slot` runs from
+            // corner `slot + 4` to corner `slot + 5`.
+            let corner = |i: usize| {
+                let angle = (i % 6) as f64 * std::f64::consts::FRAC_PI_3;
+                (cx + radius * angle.cos(), cy + radius * angle.sin())
+            };
+            for slot in cell.slots() {
+                let shared = self.neighbor_at(idx, slot).is_some_and(|n_idx| n_idx < idx);
+                if cell.walls[slot] && !shared {
+                    let ((x1, y1), (x2, y2)) = (corner(slot + 4), corner(slot + 5));
+                    data.push_str(&format!("M{:.2} {:.2}L{:.2} {:.2}", x1, y1, x2, y2));
+                }
+            }
+        }
+        data
+    }
+}
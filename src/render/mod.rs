@@ -0,0 +1,35 @@
+//! Output formats for [`Maze`]s besides the ASCII drawing of
+//! [`Maze::print`](crate::Maze::print): other text styles and images.
+
+use crate::maze::Maze;
+use crate::topology::Topology;
+use std::io;
+
+mod delta;
+mod hex;
+mod png;
+mod polar;
+mod svg;
+pub(crate) mod text;
+
+pub use png::{PngOptions, Rgb};
+pub use svg::{LineCap, SvgOptions};
+pub use text::TextStyle;
+
+impl Maze {
+    /// Fails for mazes that are not a single level of square cells, for the
+    /// outputs that can only draw those.
+    fn require_square(&self, output: &str) -> io::Result<()> {
+        let message = if self.topology != Topology::Square {
+            format!(
+                "{} only supports square mazes, not {}",
+                output, self.topology
+            )
+        } else if self.depth > 1 {
+            format!("{} only supports mazes with a single level", output)
+        } else {
+            return Ok(());
+        };
+        Err(io::Error::new(io::ErrorKind::InvalidInput, message))
+    }
+}
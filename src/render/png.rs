@@ -0,0 +1,307 @@
+use crate::maze::{Direction, Maze, UNREACHED};
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// An opaque color for raster output.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(255, 0, 0);
+
+    /// Mixes `self` and `other`, `t` going from 0 (all `self`) to 1 (all `other`).
+    fn lerp(self, other: Rgb, t: f64) -> Rgb {
+        let mix = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8;
+        Rgb(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+/// Parses `#rrggbb`, `#rgb` or one of a few color names.
+impl FromStr for Rgb {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let named = match s {
+            "black" => Some(Rgb::BLACK),
+            "white" => Some(Rgb::WHITE),
+            "red" => Some(Rgb::RED),
+            "green" => Some(Rgb(0, 128, 0)),
+            "blue" => Some(Rgb(0, 0, 255)),
+            "gray" | "grey" => Some(Rgb(128, 128, 128)),
+            _ => None,
+        };
+        if let Some(color) = named {
+            return Ok(color);
+        }
+
+        let invalid = || format!("expected a color like #ff8800, #f80 or black, got '{}'", s);
+        let hex = s.strip_prefix('#').ok_or_else(invalid)?;
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |digits: &str| u8::from_str_radix(digits, 16).map_err(|_| invalid());
+        match hex.len() {
+            6 => Ok(Rgb(
+                channel(&hex[0..2])?,
+                channel(&hex[2..4])?,
+                channel(&hex[4..6])?,
+            )),
+            3 => Ok(Rgb(
+                channel(&hex[0..1])? * 17,
+                channel(&hex[1..2])? * 17,
+                channel(&hex[2..3])? * 17,
+            )),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// Sizes and colors used by [`Maze::write_png`]. Lengths are in pixels.
+#[derive(Clone, Debug)]
+pub struct PngOptions {
+    /// Distance between two walls, so a cell is `cell_size - wall_thickness`
+    /// pixels wide inside.
+    pub cell_size: u32,
+    pub wall_thickness: u32,
+    /// Empty space around the outer wall.
+    pub margin: u32,
+    /// Color of the margin and of the passages.
+    pub background: Rgb,
+    pub wall_color: Rgb,
+    /// Color of the solution path and of the entrance and exit markers.
+    pub path_color: Rgb,
+    /// Colors the passages by their distance from the entrance, or from the
+    /// top-left cell, instead of filling them with the background color.
+    pub heat_map: bool,
+}
+
+impl Default for PngOptions {
+    fn default() -> Self {
+        PngOptions {
+            cell_size: 20,
+            wall_thickness: 2,
+            margin: 10,
+            background: Rgb::WHITE,
+            wall_color: Rgb::BLACK,
+            path_color: Rgb::RED,
+            heat_map: false,
+        }
+    }
+}
+
+/// The largest width or height of a PNG image, in pixels.
+const MAX_SIDE: u64 = (1 << 31) - 1;
+
+/// Colors of the heat map, from the start to the farthest cell.
+const HEAT: [Rgb; 3] = [Rgb(49, 54, 149), Rgb(255, 255, 191), Rgb(165, 0, 38)];
+
+impl Maze {
+    /// Writes the maze as an 8-bit RGB PNG image. The cells of `path` are
+    /// joined by a band through their centers and the entrance and exit are
+    /// marked with squares. Passages through the seams of a wrapped maze are
+    /// drawn halfway between the background and wall colors.
+    ///
+    /// Fails with [`io::ErrorKind::InvalidInput`] when the image would be
+    /// wider or taller than a PNG image can be, or too large to hold in
+    /// memory.
+    pub fn write_png<W: Write>(
+        &self,
+        out: W,
+        options: &PngOptions,
+        path: Option<&[(usize, usize)]>,
+    ) -> io::Result<()> {
+        self.require_square("PNG output")?;
+        let canvas = self.rasterize(options, path)?;
+        let mut encoder = png::Encoder::new(out, canvas.width, canvas.height);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header()?;
+        writer.write_image_data(&canvas.pixels)?;
+        writer.finish()?;
+        Ok(())
+    }
+
+    fn rasterize(
+        &self,
+        options: &PngOptions,
+        path: Option<&[(usize, usize)]>,
+    ) -> io::Result<Canvas> {
+        let size = options.cell_size.max(2);
+        let wall = options.wall_thickness.min(size - 1);
+        let margin = options.margin;
+        let side = |cells: usize| {
+            (cells as u64)
+                .checked_mul(u64::from(size))
+                .and_then(|len| len.checked_add(u64::from(wall) + 2 * u64::from(margin)))
+                .filter(|&len| len <= MAX_SIDE)
+        };
+        let (Some(width), Some(height)) = (side(self.width), side(self.height)) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "a PNG image of the {}x{} maze with {} pixel cells and a {} pixel margin \
+                     would be more than {} pixels wide or tall",
+                    self.width, self.height, size, margin, MAX_SIDE
+                ),
+            ));
+        };
+        // Every position inside the image fits in a `u32` from here on.
+        let mut canvas = Canvas::new(width as u32, height as u32, options.background)?;
+        // Top-left pixel of the wall corner above and left of cell (x, y).
+        let corner = |x: usize, y: usize| (margin + x as u32 * size, margin + y as u32 * size);
+
+        if options.heat_map {
+            let mut dist = vec![UNREACHED; self.cells.len()];
+            let (x, y) = self.entrance.unwrap_or((0, 0));
+            if !self.cells.is_empty() {
+                self.bfs(self.get_index(x, y), &mut dist);
+            }
+            let farthest = dist.iter().filter(|&&d| d != UNREACHED).max().copied();
+            let farthest = farthest.unwrap_or(0).max(1) as f64;
+            for (idx, &d) in dist.iter().enumerate() {
+                if d == UNREACHED {
+                    continue;
+                }
+                let t = d as f64 / farthest;
+                let color = if t < 0.5 {
+                    HEAT[0].lerp(HEAT[1], t * 2.0)
+                } else {
+                    HEAT[1].lerp(HEAT[2], t * 2.0 - 1.0)
+                };
+                let (left, top) = corner(idx % self.width, idx / self.width);
+                canvas.fill(left, top, size + wall, size + wall, color);
+            }
+        }
+
+        let inside = size - wall;
+        let band = (inside / 3).max(1);
+        // Top-left pixel of a `band` wide square in the middle of cell (x, y).
+        let center = |(x, y): (usize, usize)| {
+            let (left, top) = corner(x, y);
+            let offset = wall + (inside - band) / 2;
+            (left + offset, top + offset)
+        };
+        if let Some(path) = path {
+            for &cell in path {
+                let (left, top) = center(cell);
+                canvas.fill(left, top, band, band, options.path_color);
+            }
+            for step in path.windows(2) {
+                if self.jumps(step[0], step[1]) {
+                    continue;
+                }
+                let ((x1, y1), (x2, y2)) = (center(step[0]), center(step[1]));
+                canvas.fill(
+                    x1.min(x2),
+                    y1.min(y2),
+                    x1.abs_diff(x2) + band,
+                    y1.abs_diff(y2) + band,
+                    options.path_color,
+                );
+            }
+        }
+        let marker = inside.div_ceil(2);
+        for (x, y) in self.entrance.into_iter().chain(self.exit) {
+            let (left, top) = corner(x, y);
+            let offset = wall + (inside - marker) / 2;
+            canvas.fill(
+                left + offset,
+                top + offset,
+                marker,
+                marker,
+                options.path_color,
+            );
+        }
+
+        let seam_color = options.background.lerp(options.wall_color, 0.5);
+        for (idx, cell) in self.cells.iter().enumerate() {
+            let (left, top) = corner(cell.x, cell.y);
+            for direction in Direction::ALL {
+                if cell.has_wall(direction) || !self.seam(idx, direction.index()) {
+                    continue;
+                }
+                match direction {
+                    Direction::North => canvas.fill(left, top, size + wall, wall, seam_color),
+                    Direction::West => canvas.fill(left, top, wall, size + wall, seam_color),
+                    Direction::South => {
+                        canvas.fill(left, top + size, size + wall, wall, seam_color)
+                    }
+                    Direction::East => canvas.fill(left + size, top, wall, size + wall, seam_color),
+                }
+            }
+            if cell.walls[Direction::North.index()] {
+                canvas.fill(left, top, size + wall, wall, options.wall_color);
+            }
+            if cell.walls[Direction::West.index()] {
+                canvas.fill(left, top, wall, size + wall, options.wall_color);
+            }
+            if cell.walls[Direction::South.index()] {
+                canvas.fill(left, top + size, size + wall, wall, options.wall_color);
+            }
+            if cell.walls[Direction::East.index()] {
+                canvas.fill(left + size, top, wall, size + wall, options.wall_color);
+            }
+        }
+        Ok(canvas)
+    }
+}
+
+/// An RGB image in memory, three bytes per pixel, row by row.
+struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    /// A `width` by `height` image filled with `background`, or an error when
+    /// it does not fit in memory.
+    fn new(width: u32, height: u32, background: Rgb) -> io::Result<Self> {
+        let too_large = || {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("a {}x{} pixel image does not fit in memory", width, height),
+            )
+        };
+        let count = (width as usize)
+            .checked_mul(height as usize)
+            .ok_or_else(too_large)?;
+        let mut pixels = Vec::new();
+        pixels
+            .try_reserve_exact(count.checked_mul(3).ok_or_else(too_large)?)
+            .map_err(|_| too_large())?;
+        for _ in 0..count {
+            pixels.extend_from_slice(&[background.0, background.1, background.2]);
+        }
+        Ok(Canvas {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Paints a rectangle, clipped to the image.
+    fn fill(&mut self, left: u32, top: u32, width: u32, height: u32, color: Rgb) {
+        let right = (left + width).min(self.width);
+        let bottom = (top + height).min(self.height);
+        for y in top..bottom {
+            for x in left..right {
+                let i = 3 * (y as usize * self.width as usize + x as usize);
+                self.pixels[i..i + 3].copy_from_slice(&[color.0, color.1, color.2]);
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Drawings of [`Topology::Polar`](crate::Topology::Polar) mazes.
+
+use super::svg::SvgOptions;
+use crate::maze::Maze;
+use crate::topology::{COUNTERCLOCKWISE, INWARD, OUTWARD};
+use std::f64::consts::{FRAC_PI_2, TAU};
+use std::io::{self, Write};
+
+impl Maze {
+    /// Writes a polar maze unrolled into rows in the `+---+` format, the
+    /// center at the top and the outer ring at the bottom. Every cell is as
+    /// wide as the cells of the outer ring it lies over, and the left and
+    /// right edges are the same wall. Cells on `path` are marked with `*`,
+    /// the openings with `S` and `E`.
+    pub(crate) fn write_polar_ascii<W: Write>(
+        &self,
+        out: &mut W,
+        path: Option<&[(usize, usize)]>,
+    ) -> io::Result<()> {
+        let overlays = self.overlays(path);
+        let columns = 4 * self.width + 1;
+        let span = |ring: usize| self.width / self.row_len(ring);
+
+        for ring in 0..self.height {
+            let cell = |position: usize| self.cell(position / span(ring), ring);
+            let mut line = vec![b' '; columns];
+            for position in 0..self.width {
+                let wall = ring == 0 || cell(position).walls[INWARD];
+                let corner =
+                    position % span(ring) == 0 || ring > 0 && position % span(ring - 1) == 0;
+                if corner {
+                    line[4 * position] = b'+';
+                } else if wall {
+                    line[4 * position] = b'-';
+                }
+                if wall {
+                    line[4 * position + 1..4 * position + 4].copy_from_slice(b"---");
+                }
+            }
+            line[columns - 1] = b'+';
+            write_line(out, &line)?;
+
+            let mut line = vec![b' '; columns];
+            for x in 0..self.row_len(ring) {
+                let left = 4 * x * span(ring);
+                if ring > 0 && self.cell(x, ring).walls[COUNTERCLOCKWISE] {
+                    line[left] = b'|';
+                }
+                line[left + 2 * span(ring)] = overlays[self.get_index(x, ring)].label;
+            }
+            line[columns - 1] = line[0];
+            write_line(out, &line)?;
+        }
+
+        let mut line = vec![b' '; columns];
+        if let Some(ring) = self.height.checked_sub(1) {
+            for x in 0..self.row_len(ring) {
+                let left = 4 * x * span(ring);
+                line[left] = b'+';
+                if self.cell(x, ring).walls[if ring == 0 { 0 } else { OUTWARD }] {
+                    line[left + 1..left + 4 * span(ring)].fill(b'-');
+                }
+            }
+        }
+        line[columns - 1] = b'+';
+        write_line(out, &line)
+    }
+
+    /// Size of the SVG image of a polar maze. `cell_size` is the depth of a
+    /// ring.
+    pub(super) fn polar_svg_size(&self, options: &SvgOptions) -> (f64, f64) {
+        let size = 2.0 * (self.height as f64 * options.cell_size + options.margin);
+        (size, size)
+    }
+
+    /// Middle of the cell `(x, ring)` in the SVG image.
+    pub(super) fn polar_svg_center(
+        &self,
+        (x, ring): (usize, usize),
+        options: &SvgOptions,
+    ) -> (f64, f64) {
+        if ring == 0 {
+            return self.polar_point(0.0, 0.0, options);
+        }
+        let radius = (ring as f64 + 0.5) * options.cell_size;
+        self.polar_point(radius, angle(2 * x + 1, 2 * self.row_len(ring)), options)
+    }
+
+    /// Path data for every wall. A ring cell draws its inward wall as an arc
+    /// and its counterclockwise wall as a line; cells of the outer ring also
+    /// draw their outer arc.
+    pub(super) fn polar_svg_walls(&self, options: &SvgOptions) -> String {
+        let size = options.cell_size;
+        let mut data = String::new();
+        let arc = |radius: f64, from: f64, to: f64| {
+            let (x1, y1) = self.polar_point(radius, from, options);
+            let (x2, y2) = self.polar_point(radius, to, options);
+            format!(
+                "M{:.2} {:.2}A{:.2} {:.2} 0 0 1 {:.2} {:.2}",
+                x1, y1, radius, radius, x2, y2
+            )
+        };
+
+        for (idx, cell) in self.cells.iter().enumerate() {
+            let ring = cell.y;
+            let (inner, outer) = (ring as f64 * size, (ring + 1) as f64 * size);
+            if ring == 0 {
+                // Only a maze of a single ring has walls around the center.
+                if self.neighbor_at(idx, 0).is_none() && cell.walls[0] {
+                    data.push_str(&arc(outer, -FRAC_PI_2, FRAC_PI_2));
+                    data.push_str(&arc(outer, FRAC_PI_2, 3.0 * FRAC_PI_2));
+                }
+                continue;
+            }
+
+            let len = self.row_len(ring);
+            let (from, to) = (angle(cell.x, len), angle(cell.x + 1, len));
+            if cell.walls[INWARD] {
+                data.push_str(&arc(inner, from, to));
+            }
+            if self.neighbor_at(idx, OUTWARD).is_none() && cell.walls[OUTWARD] {
+                data.push_str(&arc(outer, from, to));
+            }
+            if cell.walls[COUNTERCLOCKWISE] {
+                let (x1, y1) = self.polar_point(inner, from, options);
+                let (x2, y2) = self.polar_point(outer, from, options);
+                data.push_str(&format!("M{:.2} {:.2}L{:.2} {:.2}", x1, y1, x2, y2));
+            }
+        }
+        data
+    }
+
+    /// The point at `radius` from the center of the image in direction `angle`.
+    fn polar_point(&self, radius: f64, angle: f64, options: &SvgOptions) -> (f64, f64) {
+        let center = self.height as f64 * options.cell_size + options.margin;
+        (center + radius * angle.cos(), center + radius * angle.sin())
+    }
+}
+
+/// The angle `step` steps of a ring divided into `steps` clockwise from
+/// north, in radians from the positive x axis.
+fn angle(step: usize, steps: usize) -> f64 {
+    TAU * step as f64 / steps as f64 - FRAC_PI_2
+}
+
+fn write_line<W: Write>(out: &mut W, line: &[u8]) -> io::Result<()> {
+    let line = String::from_utf8_lossy(line);
+    writeln!(out, "{}", line.trim_end())
+}
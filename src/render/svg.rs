@@ -0,0 +1,325 @@
+use crate::maze::Maze;
+use crate::topology::{Topology, DOWN, UP};
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// How the ends of wall segments are drawn, the SVG `stroke-linecap`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineCap {
+    /// Ends exactly at the corner, leaving a notch where walls meet.
+    Butt,
+    Round,
+    /// Extends past the corner by half the wall thickness so walls join cleanly.
+    #[default]
+    Square,
+}
+
+impl LineCap {
+    pub const ALL: [LineCap; 3] = [LineCap::Butt, LineCap::Round, LineCap::Square];
+}
+
+impl fmt::Display for LineCap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            LineCap::Butt => "butt",
+            LineCap::Round => "round",
+            LineCap::Square => "square",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for LineCap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LineCap::ALL
+            .into_iter()
+            .find(|cap| cap.to_string() == s)
+            .ok_or_else(|| format!("unknown line cap '{}', expected butt, round or square", s))
+    }
+}
+
+/// Sizes and colors used by [`Maze::write_svg`]. Lengths are in pixels,
+/// colors are anything SVG accepts, e.g. `black` or `#ff8800`.
+#[derive(Clone, Debug)]
+pub struct SvgOptions {
+    pub cell_size: f64,
+    pub wall_thickness: f64,
+    /// Empty space around the outer wall.
+    pub margin: f64,
+    pub line_cap: LineCap,
+    pub background: String,
+    pub wall_color: String,
+    /// Color of the solution path and of the entrance and exit markers.
+    pub path_color: String,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        SvgOptions {
+            cell_size: 20.0,
+            wall_thickness: 2.0,
+            margin: 10.0,
+            line_cap: LineCap::Square,
+            background: "white".to_string(),
+            wall_color: "black".to_string(),
+            path_color: "red".to_string(),
+        }
+    }
+}
+
+impl Maze {
+    /// Writes the maze as an SVG image. Walls are drawn as one path of line
+    /// segments, merging neighboring walls into a single segment. The cells of
+    /// `path` are joined by a line through their centers and the entrance and
+    /// exit are marked with dots. Passages through the seams of a wrapped maze
+    /// are drawn as dashed walls, and the path line breaks where it goes
+    /// through one.
+    ///
+    /// The levels of a maze with several levels are drawn one below the
+    /// other, with a triangle pointing up in cells that have stairs up and
+    /// one pointing down in cells that have stairs down.
+    pub fn write_svg<W: Write>(
+        &self,
+        out: &mut W,
+        options: &SvgOptions,
+        path: Option<&[(usize, usize)]>,
+    ) -> io::Result<()> {
+        let size = options.cell_size;
+        let margin = options.margin;
+        let (image_width, image_height) = match self.topology {
+            Topology::Square => (
+                self.width as f64 * size + 2.0 * margin,
+                self.rows() as f64 * size + (self.depth + 1) as f64 * margin,
+            ),
+            Topology::Hex => self.hex_svg_size(options),
+            Topology::Polar => self.polar_svg_size(options),
+            Topology::Delta => self.delta_svg_size(options),
+        };
+        let center = |(x, y): (usize, usize)| match self.topology {
+            Topology::Square => (
+                margin + (x as f64 + 0.5) * size,
+                self.level_top(y / self.height, options) + ((y % self.height) as f64 + 0.5) * size,
+            ),
+            Topology::Hex => self.hex_svg_center((x, y), options),
+            Topology::Polar => self.polar_svg_center((x, y), options),
+            Topology::Delta => self.delta_svg_center((x, y), options),
+        };
+        let walls = match self.topology {
+            Topology::Square => self.svg_walls(options),
+            Topology::Hex => self.hex_svg_walls(options),
+            Topology::Polar => self.polar_svg_walls(options),
+            Topology::Delta => self.delta_svg_walls(options),
+        };
+
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = image_width,
+            h = image_height
+        )?;
+        writeln!(
+            out,
+            r#"  <rect width="100%" height="100%" fill="{}"/>"#,
+            escape(&options.background)
+        )?;
+
+        let path = path.unwrap_or_default();
+        let pieces = path.chunk_by(|&from, &to| !self.jumps(from, to));
+        for piece in pieces {
+            let points: Vec<String> = piece
+                .iter()
+                .map(|&cell| {
+                    let (cx, cy) = center(cell);
+                    format!("{},{}", cx, cy)
+                })
+                .collect();
+            writeln!(
+                out,
+                r#"  <polyline points="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="round" stroke-linejoin="round"/>"#,
+                points.join(" "),
+                escape(&options.path_color),
+                options.wall_thickness
+            )?;
+        }
+        for cell in self.entrance.into_iter().chain(self.exit) {
+            let (cx, cy) = center(cell);
+            writeln!(
+                out,
+                r#"  <circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                cx,
+                cy,
+                size / 4.0,
+                escape(&options.path_color)
+            )?;
+        }
+
+        writeln!(
+            out,
+            r#"  <path d="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linecap="{}"/>"#,
+            walls,
+            escape(&options.wall_color),
+            options.wall_thickness,
+            options.line_cap
+        )?;
+        if self.depth > 1 {
+            writeln!(
+                out,
+                r#"  <path d="{}" fill="{}"/>"#,
+                self.svg_stairs(options),
+                escape(&options.wall_color)
+            )?;
+        }
+        if !self.wrap.is_none() {
+            writeln!(
+                out,
+                r#"  <path d="{}" fill="none" stroke="{}" stroke-width="{}" stroke-dasharray="{}"/>"#,
+                self.svg_seams(options),
+                escape(&options.wall_color),
+                options.wall_thickness,
+                size / 5.0
+            )?;
+        }
+        writeln!(out, "</svg>")
+    }
+
+    /// Path data for every wall. Each grid line is scanned once and runs of
+    /// consecutive walls along it become a single `M x y H x` or `M x y V y`.
+    fn svg_walls(&self, options: &SvgOptions) -> String {
+        let size = options.cell_size;
+        let margin = options.margin;
+        let mut data = String::new();
+
+        for line in 0..(self.height + 1) * self.depth {
+            let y = self.line_y(line, options);
+            for (from, to) in runs(self.width, |x| self.horizontal_wall(x, line)) {
+                let (x1, x2) = (margin + from as f64 * size, margin + to as f64 * size);
+                data.push_str(&format!("M{} {}H{}", x1, y, x2));
+            }
+        }
+        for level in 0..self.depth {
+            let (top, first) = (self.level_top(level, options), level * self.height);
+            for line in 0..=self.width {
+                let x = margin + line as f64 * size;
+                for (from, to) in runs(self.height, |y| self.vertical_wall(line, first + y)) {
+                    let (y1, y2) = (top + from as f64 * size, top + to as f64 * size);
+                    data.push_str(&format!("M{} {}V{}", x, y1, y2));
+                }
+            }
+        }
+        data
+    }
+
+    /// Path data for the stairs markers, a triangle pointing up on the left
+    /// of a cell with stairs up and one pointing down on its right for
+    /// stairs down.
+    fn svg_stairs(&self, options: &SvgOptions) -> String {
+        let size = options.cell_size;
+        let (offset, half) = (size / 4.0, size / 6.0);
+        let mut data = String::new();
+
+        for cell in &self.cells {
+            let x = options.margin + (cell.x as f64 + 0.5) * size;
+            let level = cell.y / self.height;
+            let y = self.level_top(level, options) + ((cell.y % self.height) as f64 + 0.5) * size;
+            if !cell.walls[UP] {
+                let x = x - offset;
+                data.push_str(&format!(
+                    "M{:.2} {:.2}L{:.2} {:.2}L{:.2} {:.2}Z",
+                    x - half,
+                    y + half,
+                    x,
+                    y - half,
+                    x + half,
+                    y + half
+                ));
+            }
+            if !cell.walls[DOWN] {
+                let x = x + offset;
+                data.push_str(&format!(
+                    "M{:.2} {:.2}L{:.2} {:.2}L{:.2} {:.2}Z",
+                    x - half,
+                    y - half,
+                    x,
+                    y + half,
+                    x + half,
+                    y - half
+                ));
+            }
+        }
+        data
+    }
+
+    /// Top edge of level `level` of a square maze in the SVG image. Levels
+    /// are separated by a margin.
+    fn level_top(&self, level: usize, options: &SvgOptions) -> f64 {
+        let level_height = self.height as f64 * options.cell_size + options.margin;
+        options.margin + level as f64 * level_height
+    }
+
+    /// Position of the horizontal grid line `line`, counted as in
+    /// [`Maze::horizontal_wall`], in the SVG image.
+    fn line_y(&self, line: usize, options: &SvgOptions) -> f64 {
+        let (level, line) = (line / (self.height + 1), line % (self.height + 1));
+        self.level_top(level, options) + line as f64 * options.cell_size
+    }
+
+    /// Path data for the open parts of the seams of a wrapped maze, the
+    /// outer grid lines where the edges are joined.
+    fn svg_seams(&self, options: &SvgOptions) -> String {
+        let size = options.cell_size;
+        let margin = options.margin;
+        let mut data = String::new();
+
+        for level in 0..self.depth {
+            if self.wrap.wraps_y() {
+                let first = level * (self.height + 1);
+                for line in [first, first + self.height] {
+                    let y = self.line_y(line, options);
+                    for (from, to) in runs(self.width, |x| !self.horizontal_wall(x, line)) {
+                        let (x1, x2) = (margin + from as f64 * size, margin + to as f64 * size);
+                        data.push_str(&format!("M{} {}H{}", x1, y, x2));
+                    }
+                }
+            }
+            if self.wrap.wraps_x() {
+                let (top, first) = (self.level_top(level, options), level * self.height);
+                for line in [0, self.width] {
+                    let x = margin + line as f64 * size;
+                    for (from, to) in runs(self.height, |y| !self.vertical_wall(line, first + y)) {
+                        let (y1, y2) = (top + from as f64 * size, top + to as f64 * size);
+                        data.push_str(&format!("M{} {}V{}", x, y1, y2));
+                    }
+                }
+            }
+        }
+        data
+    }
+}
+
+/// Half-open ranges of consecutive positions in `0..len` where `wall` holds.
+fn runs(len: usize, wall: impl Fn(usize) -> bool) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut start = None;
+    for i in 0..=len {
+        match (start, i < len && wall(i)) {
+            (None, true) => start = Some(i),
+            (Some(from), false) => {
+                runs.push((from, i));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    runs
+}
+
+/// Escapes a user supplied color for use inside an attribute value.
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+}
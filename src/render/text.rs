@@ -0,0 +1,174 @@
+use crate::maze::{Direction, Maze, Overlay};
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// How [`Maze::write_text`] draws a maze in a terminal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TextStyle {
+    /// The `+---+` format of [`Maze::print`].
+    #[default]
+    Ascii,
+    /// Box-drawing characters with `┌─┬┐` junctions, four characters per cell.
+    Unicode,
+    /// One quadrant block character per cell, holding the cell's north-west
+    /// corner and its north and west walls. Too small for the solution path,
+    /// the entrance and exit markers or the seams of a wrapped maze.
+    Blocks,
+}
+
+impl TextStyle {
+    pub const ALL: [TextStyle; 3] = [TextStyle::Ascii, TextStyle::Unicode, TextStyle::Blocks];
+}
+
+impl fmt::Display for TextStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TextStyle::Ascii => "ascii",
+            TextStyle::Unicode => "unicode",
+            TextStyle::Blocks => "blocks",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for TextStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TextStyle::ALL
+            .into_iter()
+            .find(|style| style.to_string() == s)
+            .ok_or_else(|| format!("unknown style '{}', expected ascii, unicode or blocks", s))
+    }
+}
+
+/// Box-drawing junctions indexed by the walls meeting at a grid point:
+/// bit 0 up, bit 1 right, bit 2 down, bit 3 left.
+pub(crate) const JUNCTIONS: [char; 16] = [
+    ' ', '╵', '╶', '└', '╷', '│', '┌', '├', '╴', '┘', '─', '┴', '┐', '┤', '┬', '┼',
+];
+
+/// Quadrant blocks indexed by their filled quarters: bit 0 upper left,
+/// bit 1 upper right, bit 2 lower left, bit 3 lower right.
+pub(crate) const QUADRANTS: [char; 16] = [
+    ' ', '▘', '▝', '▀', '▖', '▌', '▞', '▛', '▗', '▚', '▐', '▜', '▄', '▙', '▟', '█',
+];
+
+impl Maze {
+    /// Writes the maze in `style`, marking `path` and the openings as
+    /// [`Maze::write_ascii`] does where the style has room for it.
+    pub fn write_text<W: Write>(
+        &self,
+        out: &mut W,
+        style: TextStyle,
+        path: Option<&[(usize, usize)]>,
+    ) -> io::Result<()> {
+        match style {
+            TextStyle::Ascii => self.write_ascii(out, path),
+            TextStyle::Unicode => self.write_unicode(out, path),
+            TextStyle::Blocks => self.write_blocks(out),
+        }
+    }
+
+    /// Writes the maze with box-drawing characters, four characters per cell.
+    /// Passages through the seams of a wrapped maze are dashed, `┄` and `┆`.
+    pub fn write_unicode<W: Write>(
+        &self,
+        out: &mut W,
+        path: Option<&[(usize, usize)]>,
+    ) -> io::Result<()> {
+        self.require_square("the unicode style")?;
+        let overlays = self.overlays(path);
+        let mut line = String::new();
+        for y in 0..=self.height {
+            line.clear();
+            for x in 0..=self.width {
+                line.push(JUNCTIONS[self.junction(x, y)]);
+                if x < self.width {
+                    line.push_str(if self.horizontal_wall(x, y) {
+                        "───"
+                    } else if self.crossing_link(&overlays, x, y) {
+                        " * "
+                    } else if self.horizontal_seam(y) {
+                        "┄┄┄"
+                    } else {
+                        "   "
+                    });
+                }
+            }
+            writeln!(out, "{}", line)?;
+
+            if y == self.height {
+                break;
+            }
+            line.clear();
+            for x in 0..=self.width {
+                line.push(if self.vertical_wall(x, y) {
+                    '│'
+                } else if self.side_link(&overlays, x, y) {
+                    '*'
+                } else if self.vertical_seam(x) {
+                    '┆'
+                } else {
+                    ' '
+                });
+                if x < self.width {
+                    line.push(' ');
+                    line.push(char::from(overlays[self.get_index(x, y)].label));
+                    line.push(' ');
+                }
+            }
+            writeln!(out, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the maze with one quadrant block character per cell, plus a
+    /// last column and row for the east and south walls.
+    pub fn write_blocks<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        self.require_square("the blocks style")?;
+        let mut line = String::new();
+        for y in 0..=self.height {
+            line.clear();
+            for x in 0..=self.width {
+                let corner = self.junction(x, y) != 0;
+                let north = x < self.width && self.horizontal_wall(x, y);
+                let west = y < self.height && self.vertical_wall(x, y);
+                line.push(
+                    QUADRANTS[corner as usize | (north as usize) << 1 | (west as usize) << 2],
+                );
+            }
+            writeln!(out, "{}", line)?;
+        }
+        Ok(())
+    }
+
+    /// The walls meeting at the grid point in the top-left corner of cell
+    /// `(x, y)`, as an index into [`JUNCTIONS`].
+    fn junction(&self, x: usize, y: usize) -> usize {
+        let up = y > 0 && self.vertical_wall(x, y - 1);
+        let right = x < self.width && self.horizontal_wall(x, y);
+        let down = y < self.height && self.vertical_wall(x, y);
+        let left = x > 0 && self.horizontal_wall(x - 1, y);
+        up as usize | (right as usize) << 1 | (down as usize) << 2 | (left as usize) << 3
+    }
+
+    /// Whether the path crosses the horizontal grid line `line` at column `x`.
+    fn crossing_link(&self, overlays: &[Overlay], x: usize, line: usize) -> bool {
+        if line < self.height {
+            overlays[self.get_index(x, line)].links[Direction::North.index()]
+        } else {
+            line > 0 && overlays[self.get_index(x, line - 1)].links[Direction::South.index()]
+        }
+    }
+
+    /// Whether the path crosses the vertical grid line `line` at row `y`.
+    fn side_link(&self, overlays: &[Overlay], line: usize, y: usize) -> bool {
+        if line < self.width {
+            overlays[self.get_index(line, y)].links[Direction::West.index()]
+        } else {
+            line > 0 && overlays[self.get_index(line - 1, y)].links[Direction::East.index()]
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use super::{MazeSolver, Search, Solution};
+use crate::maze::{Maze, UNREACHED};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub struct AStar;
+
+impl MazeSolver for AStar {
+    fn name(&self) -> &'static str {
+        "astar"
+    }
+
+    fn description(&self) -> &'static str {
+        "A*: Dijkstra guided towards the goal by the grid distance"
+    }
+
+    fn solve(&self, maze: &Maze, start: (usize, usize), goal: (usize, usize)) -> Solution {
+        if maze.cells.is_empty() {
+            return Solution::empty();
+        }
+        let start = maze.get_index(start.0, start.1);
+        let goal = maze.get_index(goal.0, goal.1);
+        let heuristic = |idx: usize| maze.distance_bound(idx, goal);
+        let mut search = Search::new(maze, start);
+        let mut dist = vec![UNREACHED; maze.cells.len()];
+        let mut done = vec![false; maze.cells.len()];
+        dist[start] = 0;
+        // Ties on the estimated total are broken towards the cell closest to
+        // the goal, which keeps A* from fanning out across equal estimates.
+        let mut heap = BinaryHeap::from([Reverse((heuristic(start), heuristic(start), start))]);
+
+        while let Some(Reverse((_, _, idx))) = heap.pop() {
+            if done[idx] {
+                continue;
+            }
+            done[idx] = true;
+            search.expand(idx);
+            if idx == goal {
+                let path = search.path_to(goal);
+                return search.finish(Some(path));
+            }
+
+            for n_idx in maze.open_neighbors(idx) {
+                let d = dist[idx] + 1;
+                if d < dist[n_idx] {
+                    dist[n_idx] = d;
+                    search.parent[n_idx] = idx;
+                    let h = heuristic(n_idx);
+                    heap.push(Reverse((d + h, h, n_idx)));
+                }
+            }
+            search.track_frontier(heap.len());
+        }
+
+        search.finish(None)
+    }
+}
@@ -0,0 +1,43 @@
+use super::{MazeSolver, Search, Solution};
+use crate::maze::Maze;
+use std::collections::VecDeque;
+
+pub struct Bfs;
+
+impl MazeSolver for Bfs {
+    fn name(&self) -> &'static str {
+        "bfs"
+    }
+
+    fn description(&self) -> &'static str {
+        "Breadth-first search: expands cells in order of distance from the start"
+    }
+
+    fn solve(&self, maze: &Maze, start: (usize, usize), goal: (usize, usize)) -> Solution {
+        if maze.cells.is_empty() {
+            return Solution::empty();
+        }
+        let start = maze.get_index(start.0, start.1);
+        let goal = maze.get_index(goal.0, goal.1);
+        let mut search = Search::new(maze, start);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(idx) = queue.pop_front() {
+            search.expand(idx);
+            if idx == goal {
+                let path = search.path_to(goal);
+                return search.finish(Some(path));
+            }
+
+            for n_idx in maze.open_neighbors(idx) {
+                if !search.reached(n_idx) {
+                    search.parent[n_idx] = idx;
+                    queue.push_back(n_idx);
+                }
+            }
+            search.track_frontier(queue.len());
+        }
+
+        search.finish(None)
+    }
+}
@@ -0,0 +1,74 @@
+use super::{MazeSolver, Search, Solution};
+use crate::maze::Maze;
+use std::collections::VecDeque;
+
+pub struct Bidirectional;
+
+impl MazeSolver for Bidirectional {
+    fn name(&self) -> &'static str {
+        "bidirectional"
+    }
+
+    fn description(&self) -> &'static str {
+        "Bidirectional BFS: searches from both ends until the two frontiers meet"
+    }
+
+    fn solve(&self, maze: &Maze, start: (usize, usize), goal: (usize, usize)) -> Solution {
+        if maze.cells.is_empty() {
+            return Solution::empty();
+        }
+        let start = maze.get_index(start.0, start.1);
+        let goal = maze.get_index(goal.0, goal.1);
+        let mut forward = Search::new(maze, start);
+        let mut backward = Search::new(maze, goal);
+        let mut forward_queue = VecDeque::from([start]);
+        let mut backward_queue = VecDeque::from([goal]);
+        let mut frontier_peak = 1;
+        let mut meeting = (start == goal).then_some(start);
+
+        // Expand one whole level at a time, always on the smaller side, and
+        // stop as soon as either search reaches a cell the other has seen.
+        while meeting.is_none() && !forward_queue.is_empty() && !backward_queue.is_empty() {
+            let (search, queue, other) = if forward_queue.len() <= backward_queue.len() {
+                (&mut forward, &mut forward_queue, &backward)
+            } else {
+                (&mut backward, &mut backward_queue, &forward)
+            };
+
+            for _ in 0..queue.len() {
+                let idx = queue.pop_front().unwrap();
+                search.expand(idx);
+                for n_idx in maze.open_neighbors(idx) {
+                    if !search.reached(n_idx) {
+                        search.parent[n_idx] = idx;
+                        queue.push_back(n_idx);
+                        if other.reached(n_idx) && meeting.is_none() {
+                            meeting = Some(n_idx);
+                        }
+                    }
+                }
+                if meeting.is_some() {
+                    break;
+                }
+            }
+            frontier_peak = frontier_peak.max(forward_queue.len() + backward_queue.len());
+        }
+
+        let path = meeting.map(|idx| {
+            let mut path = forward.path_to(idx);
+            let mut rest = backward.path_to(idx);
+            rest.pop();
+            path.extend(rest.into_iter().rev());
+            path
+        });
+
+        let mut visited = forward.visited;
+        visited.extend(backward.visited);
+        Solution {
+            path,
+            visited,
+            frontier_peak,
+            steps: None,
+        }
+    }
+}
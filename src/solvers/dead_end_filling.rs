@@ -0,0 +1,77 @@
+use super::{MazeSolver, Search, Solution};
+use crate::maze::Maze;
+use std::collections::VecDeque;
+
+/// Fills in every dead end, and every corridor that becomes a dead end once
+/// its neighbor is filled, until only the route between start and goal is
+/// left open.
+///
+/// The filled cells are reported in [`Solution::visited`]. In a maze with loops
+/// more than one route can survive; the shortest of them is returned.
+pub struct DeadEndFilling;
+
+impl MazeSolver for DeadEndFilling {
+    fn name(&self) -> &'static str {
+        "dead-end-filling"
+    }
+
+    fn description(&self) -> &'static str {
+        "Dead-end filling: blocks off dead ends until only the solution is left"
+    }
+
+    fn solve(&self, maze: &Maze, start: (usize, usize), goal: (usize, usize)) -> Solution {
+        if maze.cells.is_empty() {
+            return Solution {
+                steps: Some(0),
+                ..Solution::empty()
+            };
+        }
+        let start = maze.get_index(start.0, start.1);
+        let goal = maze.get_index(goal.0, goal.1);
+        let mut degree: Vec<usize> = (0..maze.cells.len())
+            .map(|idx| maze.open_neighbors(idx).count())
+            .collect();
+        let mut filled = vec![false; maze.cells.len()];
+        let mut dead_ends: Vec<usize> = (0..maze.cells.len())
+            .filter(|&idx| degree[idx] <= 1 && idx != start && idx != goal)
+            .collect();
+        let mut visited = Vec::new();
+
+        while let Some(idx) = dead_ends.pop() {
+            filled[idx] = true;
+            visited.push(maze.coords(idx));
+            for n_idx in maze.open_neighbors(idx) {
+                if filled[n_idx] {
+                    continue;
+                }
+                degree[n_idx] -= 1;
+                if degree[n_idx] == 1 && n_idx != start && n_idx != goal {
+                    dead_ends.push(n_idx);
+                }
+            }
+        }
+
+        let mut search = Search::new(maze, start);
+        let mut queue = VecDeque::from([start]);
+        let mut path = None;
+        while let Some(idx) = queue.pop_front() {
+            if idx == goal {
+                path = Some(search.path_to(goal));
+                break;
+            }
+            for n_idx in maze.open_neighbors(idx) {
+                if !filled[n_idx] && !search.reached(n_idx) {
+                    search.parent[n_idx] = idx;
+                    queue.push_back(n_idx);
+                }
+            }
+        }
+
+        Solution {
+            path,
+            steps: Some(visited.len()),
+            visited,
+            frontier_peak: 0,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use super::{MazeSolver, Search, Solution};
+use crate::maze::{Maze, UNREACHED};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+pub struct Dijkstra;
+
+impl MazeSolver for Dijkstra {
+    fn name(&self) -> &'static str {
+        "dijkstra"
+    }
+
+    fn description(&self) -> &'static str {
+        "Dijkstra: expands the cell with the smallest known distance from a priority queue"
+    }
+
+    fn solve(&self, maze: &Maze, start: (usize, usize), goal: (usize, usize)) -> Solution {
+        if maze.cells.is_empty() {
+            return Solution::empty();
+        }
+        let start = maze.get_index(start.0, start.1);
+        let goal = maze.get_index(goal.0, goal.1);
+        let mut search = Search::new(maze, start);
+        let mut dist = vec![UNREACHED; maze.cells.len()];
+        let mut done = vec![false; maze.cells.len()];
+        dist[start] = 0;
+        let mut heap = BinaryHeap::from([Reverse((0, start))]);
+
+        while let Some(Reverse((d, idx))) = heap.pop() {
+            if done[idx] {
+                continue;
+            }
+            done[idx] = true;
+            search.expand(idx);
+            if idx == goal {
+                let path = search.path_to(goal);
+                return search.finish(Some(path));
+            }
+
+            for n_idx in maze.open_neighbors(idx) {
+                if d + 1 < dist[n_idx] {
+                    dist[n_idx] = d + 1;
+                    search.parent[n_idx] = idx;
+                    heap.push(Reverse((d + 1, n_idx)));
+                }
+            }
+            search.track_frontier(heap.len());
+        }
+
+        search.finish(None)
+    }
+}
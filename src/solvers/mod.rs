@@ -0,0 +1,205 @@
+use crate::maze::{Maze, UNREACHED};
+
+mod astar;
+mod bfs;
+mod bidirectional;
+mod dead_end_filling;
+mod dijkstra;
+mod tremaux;
+mod wall_follower;
+
+pub use astar::AStar;
+pub use bfs::Bfs;
+pub use bidirectional::Bidirectional;
+pub use dead_end_filling::DeadEndFilling;
+pub use dijkstra::Dijkstra;
+pub use tremaux::Tremaux;
+pub use wall_follower::{Hand, WallFollower};
+
+/// The result of running a [`MazeSolver`].
+pub struct Solution {
+    /// Every cell from start to goal, both included, or `None` when the goal
+    /// cannot be reached.
+    pub path: Option<Vec<(usize, usize)>>,
+    /// Cells in the order the solver expanded, walked through or filled them.
+    /// Walking solvers list a cell again every time they pass it.
+    pub visited: Vec<(usize, usize)>,
+    /// The largest number of cells waiting in the frontier at any time. Solvers
+    /// that walk through the maze keep no frontier and report 0.
+    pub frontier_peak: usize,
+    /// Moves made by solvers that work on the maze the way a person would,
+    /// `None` for search algorithms.
+    pub steps: Option<usize>,
+}
+
+impl Solution {
+    pub fn expanded(&self) -> usize {
+        self.visited.len()
+    }
+
+    /// The result for a maze without cells, where there is nothing to search.
+    fn empty() -> Self {
+        Solution {
+            path: None,
+            visited: Vec::new(),
+            frontier_peak: 0,
+            steps: None,
+        }
+    }
+}
+
+/// A path finding algorithm that can be selected by name.
+pub trait MazeSolver {
+    /// Identifier used on the command line, e.g. `"astar"`.
+    fn name(&self) -> &'static str;
+
+    /// One-line summary shown in `--help`.
+    fn description(&self) -> &'static str;
+
+    /// Finds a path from `start` to `goal`, which must be cells of `maze`.
+    /// A maze without any cells has no path.
+    fn solve(&self, maze: &Maze, start: (usize, usize), goal: (usize, usize)) -> Solution;
+}
+
+/// The set of solvers known to the command line.
+pub struct SolverRegistry {
+    solvers: Vec<Box<dyn MazeSolver>>,
+}
+
+impl SolverRegistry {
+    /// A registry holding every built-in solver.
+    pub fn new() -> Self {
+        let mut registry = SolverRegistry::empty();
+        registry.register(Bfs);
+        registry.register(AStar);
+        registry.register(Bidirectional);
+        registry.register(Dijkstra);
+        registry.register(WallFollower::new(Hand::Left));
+        registry.register(WallFollower::new(Hand::Right));
+        registry.register(Tremaux);
+        registry.register(DeadEndFilling);
+        registry
+    }
+
+    pub fn empty() -> Self {
+        SolverRegistry {
+            solvers: Vec::new(),
+        }
+    }
+
+    /// Adds `solver`, replacing any solver previously registered under the same name.
+    pub fn register<S: MazeSolver + 'static>(&mut self, solver: S) {
+        let solver: Box<dyn MazeSolver> = Box::new(solver);
+        match self
+            .solvers
+            .iter()
+            .position(|existing| existing.name() == solver.name())
+        {
+            Some(pos) => self.solvers[pos] = solver,
+            None => self.solvers.push(solver),
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn MazeSolver> {
+        self.iter().find(|solver| solver.name() == name)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &dyn MazeSolver> {
+        self.solvers.iter().map(|solver| solver.as_ref())
+    }
+}
+
+impl Default for SolverRegistry {
+    fn default() -> Self {
+        SolverRegistry::new()
+    }
+}
+
+impl Maze {
+    /// Finds a shortest path from `start` to `goal` with breadth-first search.
+    ///
+    /// The path lists every cell from `start` to `goal`, both included, or is
+    /// `None` when no passage connects them.
+    pub fn solve(
+        &self,
+        start: (usize, usize),
+        goal: (usize, usize),
+    ) -> Option<Vec<(usize, usize)>> {
+        Bfs.solve(self, start, goal).path
+    }
+}
+
+/// Search state shared by the solvers: the parent of every reached cell and
+/// the order in which cells were expanded.
+struct Search<'a> {
+    maze: &'a Maze,
+    parent: Vec<usize>,
+    visited: Vec<(usize, usize)>,
+    frontier_peak: usize,
+}
+
+impl<'a> Search<'a> {
+    fn new(maze: &'a Maze, start: usize) -> Self {
+        let mut parent = vec![UNREACHED; maze.cells.len()];
+        parent[start] = start;
+        Search {
+            maze,
+            parent,
+            visited: Vec::new(),
+            frontier_peak: 1,
+        }
+    }
+
+    fn reached(&self, idx: usize) -> bool {
+        self.parent[idx] != UNREACHED
+    }
+
+    fn expand(&mut self, idx: usize) {
+        self.visited.push(self.maze.coords(idx));
+    }
+
+    fn track_frontier(&mut self, len: usize) {
+        self.frontier_peak = self.frontier_peak.max(len);
+    }
+
+    /// Cells from the root of the search to `idx`.
+    fn path_to(&self, mut idx: usize) -> Vec<(usize, usize)> {
+        let mut path = vec![self.maze.coords(idx)];
+        while self.parent[idx] != idx {
+            idx = self.parent[idx];
+            path.push(self.maze.coords(idx));
+        }
+        path.reverse();
+        path
+    }
+
+    fn finish(self, path: Option<Vec<(usize, usize)>>) -> Solution {
+        Solution {
+            path,
+            visited: self.visited,
+            frontier_peak: self.frontier_peak,
+            steps: None,
+        }
+    }
+}
+
+/// Turns a walk that may double back on itself into a simple path by cutting
+/// out every loop, the way a person would strike out detours on paper.
+fn loop_erased(maze: &Maze, walk: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut position = vec![UNREACHED; maze.cells.len()];
+    let mut path: Vec<(usize, usize)> = Vec::new();
+
+    for &(x, y) in walk {
+        let idx = maze.get_index(x, y);
+        if position[idx] != UNREACHED {
+            for &(px, py) in &path[position[idx] + 1..] {
+                position[maze.get_index(px, py)] = UNREACHED;
+            }
+            path.truncate(position[idx] + 1);
+        } else {
+            position[idx] = path.len();
+            path.push((x, y));
+        }
+    }
+    path
+}
@@ -0,0 +1,77 @@
+use super::{loop_erased, MazeSolver, Solution};
+use crate::maze::Maze;
+use crate::topology::MAX_SLOTS;
+
+/// Trémaux's algorithm: walks the maze marking every passage it takes.
+///
+/// On reaching a junction it has seen before through a fresh passage it turns
+/// back; otherwise it prefers unmarked passages, then passages marked once,
+/// and never enters a passage marked twice. Passages marked once at the end
+/// form the route from the start to the goal.
+pub struct Tremaux;
+
+impl MazeSolver for Tremaux {
+    fn name(&self) -> &'static str {
+        "tremaux"
+    }
+
+    fn description(&self) -> &'static str {
+        "Trémaux: marks passages while walking and never takes one more than twice"
+    }
+
+    fn solve(&self, maze: &Maze, start: (usize, usize), goal: (usize, usize)) -> Solution {
+        if maze.cells.is_empty() {
+            return Solution {
+                steps: Some(0),
+                ..Solution::empty()
+            };
+        }
+        let mut marks = vec![[0u8; MAX_SLOTS]; maze.cells.len()];
+        let mut seen = vec![false; maze.cells.len()];
+        let mut idx = maze.get_index(start.0, start.1);
+        let goal = maze.get_index(goal.0, goal.1);
+        // The slot of the current cell the walk came in through.
+        let mut back: Option<usize> = None;
+        let mut revisit = false;
+        let mut walk = vec![start];
+        seen[idx] = true;
+
+        while idx != goal {
+            let cell = &maze.cells[idx];
+            let open = |slot: usize| !cell.walls[slot] && maze.neighbor_at(idx, slot).is_some();
+
+            let slot = match back {
+                Some(back) if revisit && marks[idx][back] == 1 => Some(back),
+                _ => cell
+                    .slots()
+                    .filter(|&slot| open(slot) && marks[idx][slot] == 0)
+                    .chain(
+                        cell.slots()
+                            .filter(|&slot| open(slot) && marks[idx][slot] == 1),
+                    )
+                    .next(),
+            };
+            let Some(slot) = slot else {
+                break;
+            };
+
+            let n_idx = maze.neighbor_at(idx, slot).unwrap();
+            let n_slot = maze.back_slot(idx, slot);
+            marks[idx][slot] += 1;
+            marks[n_idx][n_slot] += 1;
+
+            revisit = seen[n_idx];
+            seen[n_idx] = true;
+            back = Some(n_slot);
+            idx = n_idx;
+            walk.push(maze.coords(idx));
+        }
+
+        Solution {
+            path: (idx == goal).then(|| loop_erased(maze, &walk)),
+            steps: Some(walk.len() - 1),
+            visited: walk,
+            frontier_peak: 0,
+        }
+    }
+}
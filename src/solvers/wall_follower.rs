@@ -0,0 +1,107 @@
+use super::{loop_erased, MazeSolver, Solution};
+use crate::maze::{Direction, Maze};
+use crate::topology::{Topology, MAX_SLOTS};
+
+/// The hand a [`WallFollower`] keeps on the wall.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hand {
+    Left,
+    Right,
+}
+
+/// Walks with one hand on the wall until it reaches the goal.
+///
+/// This always succeeds in a perfect maze. When the start lies on a loop that
+/// does not touch the goal, the walk returns to where it began and the solver
+/// gives up.
+pub struct WallFollower {
+    pub hand: Hand,
+}
+
+impl WallFollower {
+    pub fn new(hand: Hand) -> Self {
+        WallFollower { hand }
+    }
+}
+
+impl MazeSolver for WallFollower {
+    fn name(&self) -> &'static str {
+        match self.hand {
+            Hand::Left => "wall-follower-left",
+            Hand::Right => "wall-follower-right",
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self.hand {
+            Hand::Left => "Wall follower: walks keeping the left hand on the wall",
+            Hand::Right => "Wall follower: walks keeping the right hand on the wall",
+        }
+    }
+
+    fn solve(&self, maze: &Maze, start: (usize, usize), goal: (usize, usize)) -> Solution {
+        if maze.cells.is_empty() {
+            return Solution {
+                steps: Some(0),
+                ..Solution::empty()
+            };
+        }
+        let mut idx = maze.get_index(start.0, start.1);
+        let goal = maze.get_index(goal.0, goal.1);
+        // The slot the walk came in through. Slots are numbered clockwise, so
+        // turning towards the left hand means trying the slots clockwise after
+        // it, and towards the right hand counterclockwise; going back through
+        // it comes last. The walk starts as if it had come in through the
+        // west side of a square cell, heading east, or through the last slot
+        // of any other cell. Crossing the seam of a Möbius strip mirrors the
+        // walk, so the hand on the wall switches.
+        let mut hand = self.hand;
+        let mut back = match maze.topology() {
+            Topology::Square => Direction::West.index(),
+            _ => maze.cells[idx].slots().last().unwrap_or(0),
+        };
+        let mut seen = vec![[[false; MAX_SLOTS]; 2]; maze.cells.len()];
+        let mut walk = vec![start];
+
+        while idx != goal {
+            let state = &mut seen[idx][hand as usize][back];
+            if *state {
+                break;
+            }
+            *state = true;
+
+            let cell = &maze.cells[idx];
+            let slots = cell.slots().len();
+            let step = (1..=slots).find_map(|turn| {
+                let slot = match hand {
+                    Hand::Left => (back + turn) % slots,
+                    Hand::Right => (back + slots - turn) % slots,
+                };
+                if cell.walls[slot] {
+                    return None;
+                }
+                maze.neighbor_at(idx, slot).map(|next| (slot, next))
+            });
+            let Some((slot, next)) = step else {
+                break;
+            };
+
+            if maze.mirrored(idx, slot) {
+                hand = match hand {
+                    Hand::Left => Hand::Right,
+                    Hand::Right => Hand::Left,
+                };
+            }
+            back = maze.back_slot(idx, slot);
+            idx = next;
+            walk.push(maze.coords(idx));
+        }
+
+        Solution {
+            path: (idx == goal).then(|| loop_erased(maze, &walk)),
+            steps: Some(walk.len() - 1),
+            visited: walk,
+            frontier_peak: 0,
+        }
+    }
+}
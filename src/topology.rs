@@ -0,0 +1,551 @@
+//! The shapes a [`Maze`] grid can take.
+//!
+//! Every cell has a fixed number of wall slots, numbered clockwise. A slot
+//! either faces a neighboring cell or the outside of the maze. Generators,
+//! solvers and metrics only move between cells through slots, so they work on
+//! every topology; renderers and a few generators that rely on rows and
+//! columns of square cells are specific to [`Topology::Square`]. Square grids
+//! can also [`Wrap`] around at their edges.
+
+use crate::maze::{Direction, Maze};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The largest number of walls a cell can have in any topology.
+pub(crate) const MAX_SLOTS: usize = 6;
+
+/// How the cells of a maze are shaped and connected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Topology {
+    /// Square cells in rows and columns, walls indexed by [`Direction`].
+    #[default]
+    Square,
+    /// Hexagonal cells with flat tops in columns, every odd column shifted
+    /// down by half a cell. Walls are indexed by [`HexDirection`].
+    Hex,
+    /// Rings of cells around a single center cell, see [`Maze::with_topology`]
+    /// for how they subdivide. Cell `(x, y)` is the `x`th cell of ring `y`,
+    /// counted clockwise from north. The walls of a ring cell are clockwise,
+    /// inward, counterclockwise and then one or two outward walls, in
+    /// clockwise order; the walls of the center cell face the cells of the
+    /// first ring.
+    Polar,
+    /// Triangles in rows, pointing up where `x + y` is even and down
+    /// elsewhere. The walls of a triangle pointing up are east, south and
+    /// west, those of a triangle pointing down north, east and west.
+    Delta,
+}
+
+impl Topology {
+    pub const ALL: [Topology; 4] = [
+        Topology::Square,
+        Topology::Hex,
+        Topology::Polar,
+        Topology::Delta,
+    ];
+
+    /// The largest number of walls of a cell.
+    pub fn slots(self) -> usize {
+        match self {
+            Topology::Square => 4,
+            Topology::Hex | Topology::Polar => 6,
+            Topology::Delta => 3,
+        }
+    }
+
+    /// Checks that a grid of `width` by `height` cells of this shape holds
+    /// together. A single column of triangles falls apart into pairs of
+    /// rows, as a triangle pointing down has no south side and one pointing
+    /// up no north side.
+    pub fn check_size(self, width: usize, height: usize) -> Result<(), String> {
+        if self == Topology::Delta && width < 2 && height > 2 {
+            Err(format!(
+                "a delta maze with more than 2 rows must be at least 2 triangles wide, not {}",
+                width
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Display for Topology {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Topology::Square => "square",
+            Topology::Hex => "hex",
+            Topology::Polar => "polar",
+            Topology::Delta => "delta",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Topology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Topology::ALL
+            .into_iter()
+            .find(|topology| topology.to_string() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown topology '{}', expected square, hex, polar or delta",
+                    s
+                )
+            })
+    }
+}
+
+/// Which edges of a [`Topology::Square`] grid are joined, so that passages
+/// can leave the grid on one side and come back on the other.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Wrap {
+    #[default]
+    None,
+    /// The left and right edges are joined, rolling the grid into a cylinder.
+    Horizontal,
+    /// The top and bottom edges are joined.
+    Vertical,
+    /// Both pairs of edges are joined, making a torus without any outer edge.
+    Both,
+    /// The left and right edges are joined with a half twist: leaving row
+    /// `y` on one side comes back in row `height - 1 - y` on the other.
+    Mobius,
+}
+
+impl Wrap {
+    pub const ALL: [Wrap; 5] = [
+        Wrap::None,
+        Wrap::Horizontal,
+        Wrap::Vertical,
+        Wrap::Both,
+        Wrap::Mobius,
+    ];
+
+    /// Whether the left and right edges are joined.
+    pub fn wraps_x(self) -> bool {
+        matches!(self, Wrap::Horizontal | Wrap::Both | Wrap::Mobius)
+    }
+
+    /// Whether the top and bottom edges are joined.
+    pub fn wraps_y(self) -> bool {
+        matches!(self, Wrap::Vertical | Wrap::Both)
+    }
+
+    pub fn is_none(&self) -> bool {
+        *self == Wrap::None
+    }
+}
+
+impl fmt::Display for Wrap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Wrap::None => "none",
+            Wrap::Horizontal => "horizontal",
+            Wrap::Vertical => "vertical",
+            Wrap::Both => "both",
+            Wrap::Mobius => "mobius",
+        };
+        f.write_str(name)
+    }
+}
+
+impl FromStr for Wrap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Wrap::ALL
+            .into_iter()
+            .find(|wrap| wrap.to_string() == s)
+            .ok_or_else(|| {
+                format!(
+                    "unknown wrap '{}', expected none, horizontal, vertical, both or mobius",
+                    s
+                )
+            })
+    }
+}
+
+/// One of the six sides of a hexagonal cell, clockwise from the top.
+///
+/// The discriminant is the index of the matching entry in
+/// [`Cell::walls`](crate::Cell::walls).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HexDirection {
+    North = 0,
+    NorthEast = 1,
+    SouthEast = 2,
+    South = 3,
+    SouthWest = 4,
+    NorthWest = 5,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::North,
+        HexDirection::NorthEast,
+        HexDirection::SouthEast,
+        HexDirection::South,
+        HexDirection::SouthWest,
+        HexDirection::NorthWest,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn opposite(self) -> HexDirection {
+        HexDirection::ALL[(self.index() + 3) % 6]
+    }
+
+    /// The step to the neighbor in this direction from a cell in column `x`.
+    /// Odd columns sit half a cell lower, so the diagonal neighbors of even
+    /// and odd columns are in different rows.
+    pub fn offset(self, x: usize) -> (isize, isize) {
+        let shift = if x % 2 == 1 { 1 } else { 0 };
+        match self {
+            HexDirection::North => (0, -1),
+            HexDirection::NorthEast => (1, shift - 1),
+            HexDirection::SouthEast => (1, shift),
+            HexDirection::South => (0, 1),
+            HexDirection::SouthWest => (-1, shift),
+            HexDirection::NorthWest => (-1, shift - 1),
+        }
+    }
+}
+
+/// Whether the triangle `(x, y)` of a delta maze points up.
+pub(crate) fn points_up(x: usize, y: usize) -> bool {
+    (x + y).is_multiple_of(2)
+}
+
+/// The sides of a triangle of a delta maze, in slot order.
+pub(crate) fn delta_sides(up: bool) -> [Direction; 3] {
+    if up {
+        [Direction::East, Direction::South, Direction::West]
+    } else {
+        [Direction::North, Direction::East, Direction::West]
+    }
+}
+
+/// Wall slots of the ceiling and the floor of a square cell in a maze with
+/// several levels, open where stairs lead to the level above or below.
+pub(crate) const UP: usize = 4;
+pub(crate) const DOWN: usize = 5;
+
+/// Wall slots of a polar cell outside the center. Outward walls start at
+/// `OUTWARD` and are numbered clockwise.
+pub(crate) const CLOCKWISE: usize = 0;
+pub(crate) const INWARD: usize = 1;
+pub(crate) const COUNTERCLOCKWISE: usize = 2;
+pub(crate) const OUTWARD: usize = 3;
+
+/// Number of cells in each of `rings` rings of a polar maze, as described
+/// in [`Maze::with_topology`]. A ring has one cell per unit of its inner
+/// circumference, rounded to once or twice the cells of the ring inside it.
+pub(crate) fn polar_rings(rings: usize) -> Vec<usize> {
+    let mut lens: Vec<usize> = Vec::with_capacity(rings);
+    for ring in 0..rings {
+        let len = match ring {
+            0 => 1,
+            1 => 6,
+            _ => {
+                let inner = lens[ring - 1];
+                let circumference = 2.0 * std::f64::consts::PI * ring as f64;
+                let ratio = (circumference / inner as f64).round().clamp(1.0, 2.0);
+                inner * ratio as usize
+            }
+        };
+        lens.push(len);
+    }
+    lens
+}
+
+/// Number of walls of the cells in ring `ring` of a polar maze with rings
+/// of `lens` cells.
+pub(crate) fn polar_slots(lens: &[usize], ring: usize) -> usize {
+    let outward = match lens.get(ring + 1) {
+        Some(&outer) => outer / lens[ring],
+        None => 1,
+    };
+    if ring == 0 {
+        outward
+    } else {
+        OUTWARD + outward
+    }
+}
+
+impl Maze {
+    pub fn topology(&self) -> Topology {
+        self.topology
+    }
+
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    /// Joins the edges of a square maze as `wrap` says. Meant to be called
+    /// on a new maze, before any walls are removed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the maze is not made of square cells and `wrap` is not
+    /// [`Wrap::None`].
+    pub fn wrapped(mut self, wrap: Wrap) -> Self {
+        assert!(
+            wrap.is_none() || self.topology == Topology::Square,
+            "only square mazes can wrap"
+        );
+        self.wrap = wrap;
+        self
+    }
+
+    /// Whether the maze is a single plain rectangle of square cells, the
+    /// shape the generators that work row by row or split the grid need.
+    pub fn is_plain_grid(&self) -> bool {
+        self.topology == Topology::Square && self.wrap.is_none() && self.depth == 1
+    }
+
+    /// The cell on the other side of wall `slot` of the cell at `idx`, or
+    /// `None` when the wall faces the outside.
+    pub(crate) fn neighbor_at(&self, idx: usize, slot: usize) -> Option<usize> {
+        let cell = &self.cells[idx];
+        let (dx, dy) = match self.topology {
+            Topology::Square => {
+                let (x, y) = match slot {
+                    UP => (cell.x, cell.y.checked_sub(self.height)?),
+                    DOWN => (cell.x, cell.y + self.height),
+                    _ => self.neighbor(cell.x, cell.y, Direction::ALL[slot])?,
+                };
+                return self.contains(x, y).then(|| self.get_index(x, y));
+            }
+            Topology::Hex => HexDirection::ALL[slot].offset(cell.x),
+            Topology::Polar => return self.polar_neighbor(cell.x, cell.y, slot),
+            Topology::Delta => delta_sides(points_up(cell.x, cell.y))[slot].offset(),
+        };
+        let nx = cell.x.checked_add_signed(dx)?;
+        let ny = cell.y.checked_add_signed(dy)?;
+        (nx < self.width && ny < self.height).then(|| self.get_index(nx, ny))
+    }
+
+    /// The slot of the neighbor behind wall `slot` of the cell at `idx`
+    /// that holds the same wall.
+    pub(crate) fn back_slot(&self, idx: usize, slot: usize) -> usize {
+        match self.topology {
+            Topology::Square => match slot {
+                UP => DOWN,
+                DOWN => UP,
+                _ => Direction::ALL[slot].opposite().index(),
+            },
+            Topology::Hex => HexDirection::ALL[slot].opposite().index(),
+            Topology::Delta => {
+                // Neighbors always point the other way.
+                let (x, y) = self.coords(idx);
+                let up = points_up(x, y);
+                let side = delta_sides(up)[slot].opposite();
+                delta_sides(!up).iter().position(|&s| s == side).unwrap()
+            }
+            Topology::Polar => {
+                let (x, ring) = self.coords(idx);
+                match slot {
+                    _ if ring == 0 => INWARD,
+                    CLOCKWISE => COUNTERCLOCKWISE,
+                    COUNTERCLOCKWISE => CLOCKWISE,
+                    INWARD if ring == 1 => x,
+                    INWARD => OUTWARD + x % (self.row_len(ring) / self.row_len(ring - 1)),
+                    _ => INWARD,
+                }
+            }
+        }
+    }
+
+    /// Whether wall `slot` of the cell at `idx` lies on a seam, where the
+    /// grid wraps around to the opposite edge.
+    pub(crate) fn seam(&self, idx: usize, slot: usize) -> bool {
+        if self.topology != Topology::Square || slot >= UP {
+            return false;
+        }
+        let (x, y) = self.coords(idx);
+        let row = y % self.height;
+        match Direction::ALL[slot] {
+            Direction::North => self.wrap.wraps_y() && row == 0,
+            Direction::South => self.wrap.wraps_y() && row + 1 == self.height,
+            Direction::West => self.wrap.wraps_x() && x == 0,
+            Direction::East => self.wrap.wraps_x() && x + 1 == self.width,
+        }
+    }
+
+    /// Whether passing through wall `slot` of the cell at `idx` crosses the
+    /// twisted seam of a Möbius strip, which swaps clockwise and
+    /// counterclockwise.
+    pub(crate) fn mirrored(&self, idx: usize, slot: usize) -> bool {
+        self.wrap == Wrap::Mobius && self.seam(idx, slot)
+    }
+
+    /// The side of square cell `from` that a step to the adjacent cell `to`
+    /// leaves through. On a grid that wraps around after two cells, two
+    /// sides lead to the same cell, and the one without a wall is taken.
+    pub(crate) fn step_direction(
+        &self,
+        from: (usize, usize),
+        to: (usize, usize),
+    ) -> Option<Direction> {
+        Direction::ALL
+            .into_iter()
+            .filter(|&direction| self.neighbor(from.0, from.1, direction) == Some(to))
+            .min_by_key(|&direction| self.has_wall(from.0, from.1, direction))
+    }
+
+    /// Whether a step between the adjacent cells `from` and `to` goes through
+    /// a seam or up or down stairs rather than across a grid line, so drawings
+    /// should not join them with a straight line.
+    pub(crate) fn jumps(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        if self.depth > 1 && from.1 / self.height != to.1 / self.height {
+            return true;
+        }
+        !self.wrap.is_none()
+            && self.step_direction(from, to).is_some_and(|direction| {
+                self.seam(self.get_index(from.0, from.1), direction.index())
+            })
+    }
+
+    /// The cell behind wall `slot` of cell `x` of ring `ring`.
+    fn polar_neighbor(&self, x: usize, ring: usize, slot: usize) -> Option<usize> {
+        if ring == 0 {
+            return self.contains(slot, 1).then(|| self.get_index(slot, 1));
+        }
+        let len = self.row_len(ring);
+        match slot {
+            CLOCKWISE => Some(self.get_index((x + 1) % len, ring)),
+            COUNTERCLOCKWISE => Some(self.get_index((x + len - 1) % len, ring)),
+            INWARD => {
+                let ratio = len / self.row_len(ring - 1);
+                Some(self.get_index(x / ratio, ring - 1))
+            }
+            _ if ring + 1 == self.height => None,
+            _ => {
+                let ratio = self.row_len(ring + 1) / len;
+                Some(self.get_index(x * ratio + slot - OUTWARD, ring + 1))
+            }
+        }
+    }
+
+    /// The slot of the cell at `idx` that faces the cell at `other`.
+    pub(crate) fn slot_towards(&self, idx: usize, other: usize) -> Option<usize> {
+        self.cells[idx]
+            .slots()
+            .find(|&slot| self.neighbor_at(idx, slot) == Some(other))
+    }
+
+    /// A wall of the cell at `idx` that faces the outside, used for the
+    /// entrance and exit, or `None` for inner cells.
+    pub(crate) fn boundary_slot(&self, idx: usize) -> Option<usize> {
+        match self.topology {
+            Topology::Square => {
+                let cell = &self.cells[idx];
+                self.boundary_side(cell.x, cell.y).map(Direction::index)
+            }
+            Topology::Hex | Topology::Polar | Topology::Delta => self.cells[idx]
+                .slots()
+                .find(|&slot| self.neighbor_at(idx, slot).is_none()),
+        }
+    }
+
+    /// A lower bound on the number of steps between two cells, used by A*.
+    pub(crate) fn distance_bound(&self, a: usize, b: usize) -> usize {
+        let (a, b) = (&self.cells[a], &self.cells[b]);
+        match self.topology {
+            Topology::Square if !self.is_plain_grid() => {
+                // Every level changes at a flight of stairs, and going through
+                // a seam may be shorter than staying inside.
+                let (a_level, a_row) = (a.y / self.height, a.y % self.height);
+                let (b_level, b_row) = (b.y / self.height, b.y % self.height);
+                let levels = a_level.abs_diff(b_level);
+                let dx = a.x.abs_diff(b.x);
+                let dy = a_row.abs_diff(b_row);
+                let wrapped = |d: usize, len: usize| d.min(len - d);
+                levels
+                    + match self.wrap {
+                        Wrap::Mobius => {
+                            let twisted = (self.height - 1 - a_row).abs_diff(b_row);
+                            (dx + dy).min(self.width - dx + twisted)
+                        }
+                        wrap => {
+                            let dx = if wrap.wraps_x() {
+                                wrapped(dx, self.width)
+                            } else {
+                                dx
+                            };
+                            let dy = if wrap.wraps_y() {
+                                wrapped(dy, self.height)
+                            } else {
+                                dy
+                            };
+                            dx + dy
+                        }
+                    }
+            }
+            // Every step moves one column or one row.
+            Topology::Square | Topology::Delta => a.x.abs_diff(b.x) + a.y.abs_diff(b.y),
+            Topology::Hex => {
+                // Cube coordinates of the offset columns, where the distance
+                // is the largest difference along any of the three axes.
+                let cube = |x: usize, y: usize| {
+                    let (x, y) = (x as isize, y as isize);
+                    let z = y - (x - (x & 1)) / 2;
+                    (x, z, -x - z)
+                };
+                let (ax, ay, az) = cube(a.x, a.y);
+                let (bx, by, bz) = cube(b.x, b.y);
+                ax.abs_diff(bx).max(ay.abs_diff(by)).max(az.abs_diff(bz))
+            }
+            // Every step moves at most one ring in or out.
+            Topology::Polar => a.y.abs_diff(b.y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generators::Registry;
+    use crate::maze::UNREACHED;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn narrow_delta_grids_are_rejected_or_connected() {
+        assert!(Topology::Delta.check_size(1, 3).is_err());
+        assert!(Topology::Delta.check_size(1, 2).is_ok());
+
+        let registry = Registry::new();
+        for width in 0..4 {
+            for height in 0..5 {
+                if Topology::Delta.check_size(width, height).is_err() {
+                    continue;
+                }
+                for generator in registry.iter() {
+                    let mut maze = Maze::with_topology(Topology::Delta, width, height);
+                    if !generator.supports(&maze) || maze.cells.is_empty() {
+                        continue;
+                    }
+                    generator.generate(&mut maze, &mut ChaCha8Rng::seed_from_u64(1));
+                    let mut dist = vec![UNREACHED; maze.cells.len()];
+                    let reached = maze.bfs(0, &mut dist).len();
+                    assert_eq!(
+                        reached,
+                        maze.cells.len(),
+                        "{} on {}x{}",
+                        generator.name(),
+                        width,
+                        height
+                    );
+                }
+            }
+        }
+    }
+}